@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use isosurf::{
+    simd::{LaneWidth, SimdF32, SinCosPrecision},
+    water::{
+        BasePositions, WavePhasors, WaterWaves, WaveParameters, create_water_mesh, displace_water_surface,
+        displace_water_surface_from_phasors, displace_water_surface_with_lanes,
+        displace_water_surface_with_precision, displace_water_vertices_with_lanes,
+    },
+};
+use std::hint::black_box;
+use wide::f32x8;
+
+const TIME: f32 = 3.0;
+
+/// The displacement loop as it was before `BasePositions`: x and z gathered out of `Vec3`s chunk by chunk
+fn displace_gathered(base_positions: &[Vec3], waves: &[WaveParameters], time: f32, pos_data: &mut [[f32; 3]]) {
+    for (base_chunk, pos_chunk) in base_positions.chunks(8).zip(pos_data.chunks_mut(8)) {
+        let mut gathered_x = [0.0; 8];
+        let mut gathered_z = [0.0; 8];
+        for (lane, base_pos) in base_chunk.iter().enumerate() {
+            gathered_x[lane] = base_pos.x;
+            gathered_z[lane] = base_pos.z;
+        }
+        let positions_x = f32x8::load(&gathered_x[..base_chunk.len()]);
+        let positions_z = f32x8::load(&gathered_z[..base_chunk.len()]);
+
+        let (mut total_dx, mut total_dy, mut total_dz) = (f32x8::ZERO, f32x8::ZERO, f32x8::ZERO);
+        for wave in waves {
+            let dot_products = positions_x * wave.direction.x + positions_z * wave.direction.y;
+            let phases = dot_products * wave.wave_number - (wave.speed * time - wave.phase);
+            let (sin_phases, cos_phases) = phases.sin_cos();
+            let q_a_cos = cos_phases * (wave.steepness * wave.amplitude);
+            total_dx += q_a_cos * wave.direction.x;
+            total_dz += q_a_cos * wave.direction.y;
+            total_dy += sin_phases * wave.amplitude;
+        }
+
+        let (dx, dy, dz) = (total_dx.to_array(), total_dy.to_array(), total_dz.to_array());
+        for (lane, (base_pos, pos)) in base_chunk.iter().zip(pos_chunk).enumerate() {
+            *pos = [base_pos.x + dx[lane], dy[lane], base_pos.z + dz[lane]];
+        }
+    }
+}
+
+fn positions(c: &mut Criterion) {
+    let waves = WaterWaves::default();
+    let mut group = c.benchmark_group("positions");
+
+    for grid_size in [64, 200] {
+        let (_, gathered) = create_water_mesh(grid_size, 100.0);
+        let base_positions = BasePositions::new(&gathered);
+        let mut cached = base_positions.clone();
+        cached.cache_wave_dots(&waves.waves);
+        let mut pos_data = vec![[0.0; 3]; gathered.len()];
+
+        group.bench_function(BenchmarkId::new("gather_x8", grid_size), |b| {
+            b.iter(|| displace_gathered(black_box(&gathered), &waves.waves, TIME, &mut pos_data))
+        });
+        group.bench_function(BenchmarkId::new("soa_x8", grid_size), |b| {
+            b.iter(|| {
+                let base_positions = black_box(&base_positions);
+                displace_water_vertices_with_lanes(LaneWidth::X8, base_positions, &waves.waves, TIME, &mut pos_data)
+            })
+        });
+        group.bench_function(BenchmarkId::new("soa_x8_cached_dots", grid_size), |b| {
+            b.iter(|| {
+                let cached = black_box(&cached);
+                displace_water_vertices_with_lanes(LaneWidth::X8, cached, &waves.waves, TIME, &mut pos_data)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn surface(c: &mut Criterion) {
+    let waves = WaterWaves::default();
+    let mut group = c.benchmark_group("surface");
+
+    let base_positions = BasePositions::new(&create_water_mesh(200, 100.0).1);
+    let mut cached = base_positions.clone();
+    cached.cache_wave_dots(&waves.waves);
+    let count = base_positions.len();
+    let (mut pos_data, mut normals, mut tangents) =
+        (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count]);
+
+    for (name, base_positions) in [("soa", &base_positions), ("soa_cached_dots", &cached)] {
+        for lane_width in LaneWidth::ALL {
+            group.bench_function(BenchmarkId::new(name, lane_width.lanes()), |b| {
+                b.iter(|| {
+                    displace_water_surface_with_lanes(
+                        lane_width,
+                        black_box(base_positions),
+                        &waves.waves,
+                        TIME,
+                        &mut pos_data,
+                        &mut normals,
+                        &mut tangents,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn sin_cos_precision(c: &mut Criterion) {
+    let waves = WaterWaves::default();
+    let mut group = c.benchmark_group("sin_cos_precision");
+
+    let base_positions = BasePositions::new(&create_water_mesh(200, 100.0).1);
+    let count = base_positions.len();
+    let (mut pos_data, mut normals, mut tangents) =
+        (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count]);
+
+    for precision in SinCosPrecision::ALL {
+        group.bench_function(BenchmarkId::from_parameter(format!("{precision:?}")), |b| {
+            b.iter(|| {
+                displace_water_surface_with_precision(
+                    precision,
+                    black_box(&base_positions),
+                    &waves.waves,
+                    TIME,
+                    &mut pos_data,
+                    &mut normals,
+                    &mut tangents,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn phasors(c: &mut Criterion) {
+    let waves = WaterWaves::default();
+    let mut group = c.benchmark_group("phasors");
+
+    let base_positions = BasePositions::new(&create_water_mesh(200, 100.0).1);
+    let count = base_positions.len();
+    let (mut pos_data, mut normals, mut tangents) =
+        (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count]);
+    let dt = 1.0 / 64.0;
+
+    group.bench_function("evaluated", |b| {
+        let mut time = TIME;
+        b.iter(|| {
+            time += dt;
+            let base_positions = black_box(&base_positions);
+            displace_water_surface(base_positions, &waves.waves, time, &mut pos_data, &mut normals, &mut tangents)
+        })
+    });
+    group.bench_function("rotated", |b| {
+        let mut phasors = WavePhasors::new(u32::MAX);
+        let mut time = TIME;
+        b.iter(|| {
+            time += dt;
+            let waves = &waves.waves;
+            phasors.advance(black_box(&base_positions), waves, time, dt);
+            let (pos_data, normals, tangents) = (&mut pos_data, &mut normals, &mut tangents);
+            displace_water_surface_from_phasors(&phasors, &base_positions, waves, pos_data, normals, tangents)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, positions, surface, sin_cos_precision, phasors);
+criterion_main!(benches);
@@ -0,0 +1,125 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use isosurf::{
+    spectrum::{OceanSpectrum, SpectrumSampling},
+    surfboard::{FloatingBody, Surfboard, update_surfboard_physics},
+    water::{
+        BasePositions, HeightQuerySettings, WaterDisplacementSettings, WaterSurface, WaterWaves,
+        calculate_gerstner_displacement, create_water_mesh, displace_water_vertices, update_water_vertices,
+    },
+};
+use std::hint::black_box;
+
+const TIME: f32 = 3.0;
+const WORLD_SIZE: f32 = 100.0;
+
+/// The hand-tuned default waves and a 16 component spectrum, so costs that scale with the wave count show up
+fn wave_sets() -> [(&'static str, WaterWaves); 2] {
+    let spectrum = WaterWaves::from_spectrum(&OceanSpectrum::default(), &SpectrumSampling::default());
+    [("default", WaterWaves::default()), ("spectrum_16", spectrum)]
+}
+
+/// Headless app that only runs `update_water_vertices` on one `grid_size` x `grid_size` water mesh
+fn water_mesh_app(grid_size: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_resource::<WaterDisplacementSettings>()
+        .add_systems(Update, update_water_vertices);
+
+    let (mesh, base_positions) = create_water_mesh(grid_size, WORLD_SIZE);
+    let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(mesh);
+    app.world_mut().spawn((
+        Mesh3d(mesh),
+        WaterSurface {
+            grid_size,
+            world_size: WORLD_SIZE,
+            vertex_count: base_positions.len(),
+            base_positions: BasePositions::new(&base_positions),
+            seams: Vec::new(),
+        },
+        WaterWaves::default(),
+    ));
+
+    // The first update inserts the normal and tangent attributes
+    app.update();
+    app
+}
+
+fn gerstner(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gerstner");
+    let (_, positions) = create_water_mesh(100, WORLD_SIZE);
+    let base_positions = BasePositions::new(&positions);
+    let mut pos_data = vec![[0.0; 3]; positions.len()];
+    group.throughput(Throughput::Elements(positions.len() as u64));
+
+    for (name, waves) in wave_sets() {
+        group.bench_function(BenchmarkId::new("scalar", name), |b| {
+            b.iter(|| {
+                for (pos, base_pos) in pos_data.iter_mut().zip(&positions) {
+                    let mut displacement = Vec3::ZERO;
+                    for wave in &waves.waves {
+                        let (x, z, y) = calculate_gerstner_displacement(black_box(base_pos.xz()), wave, TIME);
+                        displacement += Vec3::new(x, y, z);
+                    }
+                    *pos = (*base_pos + displacement).to_array();
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("simd", name), |b| {
+            b.iter(|| displace_water_vertices(black_box(&base_positions), &waves.waves, TIME, &mut pos_data))
+        });
+    }
+    group.finish();
+}
+
+fn water_mesh_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_water_vertices");
+
+    for grid_size in [50, 100, 200, 400] {
+        let mut app = water_mesh_app(grid_size);
+        group.throughput(Throughput::Elements((grid_size * grid_size) as u64));
+        group.bench_function(BenchmarkId::from_parameter(grid_size), |b| b.iter(|| app.update()));
+    }
+    group.finish();
+}
+
+fn mesh_normals(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compute_normals");
+
+    for grid_size in [50, 100, 200, 400] {
+        let mut app = water_mesh_app(grid_size);
+        let mut meshes = app.world_mut().resource_mut::<Assets<Mesh>>();
+        let (_, mesh) = meshes.iter_mut().next().unwrap();
+        let mut mesh = mesh.clone();
+        assert!(matches!(mesh.attribute(Mesh::ATTRIBUTE_NORMAL), Some(VertexAttributeValues::Float32x3(_))));
+
+        group.throughput(Throughput::Elements((grid_size * grid_size) as u64));
+        group.bench_function(BenchmarkId::from_parameter(grid_size), |b| b.iter(|| mesh.compute_normals()));
+    }
+    group.finish();
+}
+
+fn surfboard_physics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_surfboard_physics");
+
+    for body_count in [1, 10, 100, 1000] {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<HeightQuerySettings>()
+            .add_systems(Update, update_surfboard_physics);
+        app.world_mut().spawn((Transform::IDENTITY, GlobalTransform::IDENTITY, WaterWaves::default()));
+        for i in 0..body_count {
+            let position = Vec3::new((i % 32) as f32 * 4.0, 0.0, (i / 32) as f32 * 2.0);
+            app.world_mut()
+                .spawn((Transform::from_translation(position), FloatingBody::default(), Surfboard::default()));
+        }
+
+        group.throughput(Throughput::Elements(body_count));
+        group.bench_function(BenchmarkId::from_parameter(body_count), |b| b.iter(|| app.update()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, gerstner, water_mesh_update, mesh_normals, surfboard_physics);
+criterion_main!(benches);
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+
+use crate::{
+    simd::{LaneWidth, MAX_LANES, SimdF32, lane_dispatch},
+    water::{GerstnerSums, HeightQuerySettings, WaveParameters},
+};
+
+/// Results of `sample_water_batch`, kept between calls so the buffers are only allocated once
+#[derive(Debug, Default, Clone)]
+pub struct WaterSampleBatch {
+    pub heights: Vec<f32>,
+    pub normals: Vec<Vec3>,
+    pub velocities: Vec<Vec3>,
+}
+
+impl WaterSampleBatch {
+    fn resize(&mut self, len: usize) {
+        self.heights.resize(len, 0.0);
+        self.normals.resize(len, Vec3::Y);
+        self.velocities.resize(len, Vec3::ZERO);
+    }
+}
+
+/// Batched `get_displaced_wave_height` for many world XZ positions given as separate x and z slices
+pub fn get_displaced_wave_heights(
+    positions_x: &[f32],
+    positions_z: &[f32],
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+    heights: &mut [f32],
+) {
+    let lane_width = LaneWidth::detect();
+    get_displaced_wave_heights_with_lanes(lane_width, positions_x, positions_z, waves, time, settings, heights);
+}
+
+lane_dispatch! {
+    /// `get_displaced_wave_heights` at a fixed lane width instead of the widest one the CPU supports
+    pub fn get_displaced_wave_heights_with_lanes => displaced_heights_lanes(
+        positions_x: &[f32],
+        positions_z: &[f32],
+        waves: &[WaveParameters],
+        time: f32,
+        settings: &HeightQuerySettings,
+        heights: &mut [f32],
+    )
+}
+
+#[inline(always)]
+fn displaced_heights_lanes<S: SimdF32>(
+    positions_x: &[f32],
+    positions_z: &[f32],
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+    heights: &mut [f32],
+) {
+    assert_eq!(positions_x.len(), positions_z.len());
+    assert_eq!(positions_x.len(), heights.len());
+    // Without waves the water is flat, so skip solving for the undisplaced positions
+    if waves.is_empty() {
+        heights.fill(0.0);
+        return;
+    }
+    let lanes = S::LANES;
+    for ((xs, zs), out) in positions_x.chunks(lanes).zip(positions_z.chunks(lanes)).zip(heights.chunks_mut(lanes)) {
+        let (grid_x, grid_z) = find_undisplaced_positions(S::load(xs), S::load(zs), waves, time, settings);
+
+        let mut height = S::splat(0.0);
+        for wave in waves {
+            let (sin_phase, _) = wave_phase(grid_x, grid_z, wave, time).sin_cos();
+            height += S::splat(wave.amplitude) * sin_phase;
+        }
+        height.store(out);
+    }
+}
+
+/// Batched `sample_water` returning heights, surface normals and orbital velocities
+pub fn sample_water_batch(
+    positions_x: &[f32],
+    positions_z: &[f32],
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+    batch: &mut WaterSampleBatch,
+) {
+    sample_water_batch_with_lanes(LaneWidth::detect(), positions_x, positions_z, waves, time, settings, batch);
+}
+
+lane_dispatch! {
+    /// `sample_water_batch` at a fixed lane width instead of the widest one the CPU supports
+    pub fn sample_water_batch_with_lanes => sample_water_lanes(
+        positions_x: &[f32],
+        positions_z: &[f32],
+        waves: &[WaveParameters],
+        time: f32,
+        settings: &HeightQuerySettings,
+        batch: &mut WaterSampleBatch,
+    )
+}
+
+#[inline(always)]
+fn sample_water_lanes<S: SimdF32>(
+    positions_x: &[f32],
+    positions_z: &[f32],
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+    batch: &mut WaterSampleBatch,
+) {
+    assert_eq!(positions_x.len(), positions_z.len());
+    batch.resize(positions_x.len());
+    // Flat, still water, as for the heights
+    if waves.is_empty() {
+        batch.heights.fill(0.0);
+        batch.normals.fill(Vec3::Y);
+        batch.velocities.fill(Vec3::ZERO);
+        return;
+    }
+    let lanes = S::LANES;
+
+    for (chunk, (xs, zs)) in positions_x.chunks(lanes).zip(positions_z.chunks(lanes)).enumerate() {
+        let (grid_x, grid_z) = find_undisplaced_positions(S::load(xs), S::load(zs), waves, time, settings);
+
+        let mut sums = GerstnerSums::<S>::new();
+        for wave in waves {
+            let (sin_phase, cos_phase) = wave_phase(grid_x, grid_z, wave, time).sin_cos();
+            sums.add_wave(wave, sin_phase, cos_phase);
+            sums.add_velocity(wave, sin_phase, cos_phase);
+        }
+
+        let [normal_x, normal_y, normal_z] = sums.normal();
+        let length = (normal_x * normal_x + normal_y * normal_y + normal_z * normal_z).sqrt();
+
+        let start = chunk * lanes;
+        let end = (start + lanes).min(positions_x.len());
+        sums.displacement[1].store(&mut batch.heights[start..end]);
+
+        let [velocity_x, velocity_y, velocity_z] = sums.velocity;
+        let lane_values =
+            [normal_x / length, normal_y / length, normal_z / length, velocity_x, velocity_y, velocity_z];
+        let [n_x, n_y, n_z, v_x, v_y, v_z] = lane_values.map(|value| {
+            let mut lanes = [0.0; MAX_LANES];
+            value.store(&mut lanes[..S::LANES]);
+            lanes
+        });
+        let outputs = batch.normals[start..end].iter_mut().zip(&mut batch.velocities[start..end]);
+        for (lane, (normal, velocity)) in outputs.enumerate() {
+            *normal = Vec3::new(n_x[lane], n_y[lane], n_z[lane]);
+            *velocity = Vec3::new(v_x[lane], v_y[lane], v_z[lane]);
+        }
+    }
+}
+
+/// k * (d · p) - ω t + φ for every lane
+#[inline(always)]
+pub(crate) fn wave_phase<S: SimdF32>(grid_x: S, grid_z: S, wave: &WaveParameters, time: f32) -> S {
+    let dot_products = grid_x * S::splat(wave.direction.x) + grid_z * S::splat(wave.direction.y);
+    S::splat(wave.wave_number) * dot_products - S::splat(wave.speed * time - wave.phase)
+}
+
+/// Lane-wise `find_undisplaced_position`, iterating until every lane has converged
+fn find_undisplaced_positions<S: SimdF32>(
+    target_x: S,
+    target_z: S,
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+) -> (S, S) {
+    let (mut grid_x, mut grid_z) = (target_x, target_z);
+    let tolerance_squared = S::splat(settings.tolerance * settings.tolerance);
+    let min_determinant = S::splat(1e-3);
+
+    for _ in 0..settings.max_iterations {
+        let (mut displaced_x, mut displaced_z) = (grid_x, grid_z);
+        let (mut j_xx, mut j_xz, mut j_zz) = (S::splat(1.0), S::splat(0.0), S::splat(1.0));
+
+        for wave in waves {
+            let (sin_phase, cos_phase) = wave_phase(grid_x, grid_z, wave, time).sin_cos();
+            let dir_x = S::splat(wave.direction.x);
+            let dir_z = S::splat(wave.direction.y);
+            let q_a = wave.steepness * wave.amplitude;
+
+            let q_a_cos = S::splat(q_a) * cos_phase;
+            displaced_x += q_a_cos * dir_x;
+            displaced_z += q_a_cos * dir_z;
+
+            let q_a_k_sin = S::splat(q_a * wave.wave_number) * sin_phase;
+            j_xx -= q_a_k_sin * dir_x * dir_x;
+            j_xz -= q_a_k_sin * dir_x * dir_z;
+            j_zz -= q_a_k_sin * dir_z * dir_z;
+        }
+
+        let error_x = displaced_x - target_x;
+        let error_z = displaced_z - target_z;
+        if (error_x * error_x + error_z * error_z).lt(tolerance_squared).all() {
+            break;
+        }
+
+        // Newton step through the symmetric 2x2 Jacobian, fixed-point step near folded crests
+        let determinant = j_xx * j_zz - j_xz * j_xz;
+        let newton = determinant.abs().gt(min_determinant);
+        let step_x = (j_zz * error_x - j_xz * error_z) / determinant;
+        let step_z = (j_xx * error_z - j_xz * error_x) / determinant;
+        grid_x -= newton.select(step_x, error_x);
+        grid_z -= newton.select(step_z, error_z);
+    }
+
+    (grid_x, grid_z)
+}
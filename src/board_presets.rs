@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+
+use crate::{
+    buoyancy_points::BuoyancyPointsBuilder,
+    hull_buoyancy::BuoyancyHull,
+    surfboard::{FloatingBody, Surfboard, create_surfboard_mesh},
+    surfboard_shape::TailShape,
+};
+
+const POINT_SPACING: f32 = 0.25; // Between the fallback buoyancy points fitted through a preset's board
+const POINT_SUBSAMPLES: u32 = 2; // Boards are thin, so a coarse fill is plenty
+
+/// Common kinds of board, each with typical measurements and weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoardPreset {
+    Longboard,  // 9'6" single fin noserider
+    Funboard,   // 7'6" mini-mal
+    Shortboard, // 6'0" thruster
+    Fish,       // 5'8" twin-fin swallow tail
+    Gun,        // 7'6" pintail for big waves
+    Sup,        // 10'6" stand-up paddleboard
+    Bodyboard,  // 42" foam board ridden lying down
+}
+
+impl BoardPreset {
+    pub const ALL: [Self; 7] = [
+        Self::Longboard,
+        Self::Funboard,
+        Self::Shortboard,
+        Self::Fish,
+        Self::Gun,
+        Self::Sup,
+        Self::Bodyboard,
+    ];
+
+    /// Shaper's measurements of the board
+    pub fn surfboard(self) -> Surfboard {
+        match self {
+            Self::Longboard => Surfboard {
+                length: 2.9,
+                width: 0.58,
+                thickness: 0.076,
+                nose_width: 0.46,
+                tail_width: 0.36,
+                wide_point: 0.0,
+                nose_rocker: 0.1,
+                tail_rocker: 0.05,
+                nose_thickness: 0.45,
+                tail_thickness: 0.5,
+                rail_apex: 0.5,
+                rail_fullness: 2.5,
+                tail_shape: TailShape::Square,
+            },
+            Self::Funboard => Surfboard {
+                length: 2.29,
+                width: 0.56,
+                thickness: 0.07,
+                nose_width: 0.41,
+                tail_width: 0.36,
+                wide_point: 0.0,
+                nose_rocker: 0.1,
+                tail_rocker: 0.05,
+                nose_thickness: 0.45,
+                tail_thickness: 0.5,
+                rail_apex: 0.45,
+                rail_fullness: 2.5,
+                tail_shape: TailShape::Squash,
+            },
+            Self::Shortboard => Surfboard {
+                length: 1.83,
+                width: 0.49,
+                thickness: 0.062,
+                nose_width: 0.3,
+                tail_width: 0.37,
+                wide_point: -0.05,
+                nose_rocker: 0.12,
+                tail_rocker: 0.06,
+                nose_thickness: 0.35,
+                tail_thickness: 0.45,
+                rail_apex: 0.35,
+                rail_fullness: 3.5,
+                tail_shape: TailShape::Squash,
+            },
+            Self::Fish => Surfboard {
+                length: 1.73,
+                width: 0.53,
+                thickness: 0.065,
+                nose_width: 0.38,
+                tail_width: 0.41,
+                wide_point: 0.0,
+                nose_rocker: 0.08,
+                tail_rocker: 0.03,
+                nose_thickness: 0.4,
+                tail_thickness: 0.5,
+                rail_apex: 0.45,
+                rail_fullness: 3.0,
+                tail_shape: TailShape::Swallow,
+            },
+            Self::Gun => Surfboard {
+                length: 2.29,
+                width: 0.5,
+                thickness: 0.07,
+                nose_width: 0.3,
+                tail_width: 0.3,
+                wide_point: 0.1,
+                nose_rocker: 0.14,
+                tail_rocker: 0.07,
+                nose_thickness: 0.4,
+                tail_thickness: 0.45,
+                rail_apex: 0.4,
+                rail_fullness: 3.0,
+                tail_shape: TailShape::Pin,
+            },
+            Self::Sup => Surfboard {
+                length: 3.2,
+                width: 0.81,
+                thickness: 0.12,
+                nose_width: 0.62,
+                tail_width: 0.56,
+                wide_point: 0.0,
+                nose_rocker: 0.08,
+                tail_rocker: 0.03,
+                nose_thickness: 0.5,
+                tail_thickness: 0.6,
+                rail_apex: 0.5,
+                rail_fullness: 4.0,
+                tail_shape: TailShape::Square,
+            },
+            Self::Bodyboard => Surfboard {
+                length: 1.07,
+                width: 0.55,
+                thickness: 0.055,
+                nose_width: 0.5,
+                tail_width: 0.42,
+                wide_point: 0.1,
+                nose_rocker: 0.02,
+                tail_rocker: 0.0,
+                nose_thickness: 0.8,
+                tail_thickness: 0.8,
+                rail_apex: 0.6,
+                rail_fullness: 5.0,
+                tail_shape: TailShape::Swallow,
+            },
+        }
+    }
+
+    /// Weight of the finished board in kg, with fins and leash
+    pub fn mass(self) -> f32 {
+        match self {
+            Self::Longboard => 5.5,
+            Self::Funboard => 4.0,
+            Self::Shortboard => 2.8,
+            Self::Fish => 3.0,
+            Self::Gun => 3.6,
+            Self::Sup => 11.0,
+            Self::Bodyboard => 1.4,
+        }
+    }
+
+    /// Height of a rider's centre of mass above the deck in m, for how the board is usually ridden
+    pub fn rider_height(self) -> f32 {
+        match self {
+            Self::Longboard | Self::Sup => 1.0, // Standing upright
+            Self::Funboard => 0.9,
+            Self::Shortboard | Self::Fish | Self::Gun => 0.8, // Crouched low
+            Self::Bodyboard => 0.15,                          // Lying prone
+        }
+    }
+
+    /// A `FloatingBody` as heavy as the board, on buoyancy points fitted through its shape
+    pub fn floating_body(self) -> FloatingBody {
+        let surfboard = self.surfboard();
+        self.floating_body_from_mesh(&surfboard, &create_surfboard_mesh(&surfboard))
+    }
+
+    fn floating_body_from_mesh(self, surfboard: &Surfboard, mesh: &Mesh) -> FloatingBody {
+        let (buoyancy_points, point_volumes, point_height) = BuoyancyPointsBuilder::mesh(mesh)
+            .expect("surfboard mesh is an indexed triangle list")
+            .with_spacing(POINT_SPACING)
+            .with_subsamples(POINT_SUBSAMPLES)
+            .points();
+        FloatingBody {
+            buoyancy_points,
+            point_volumes,
+            point_height,
+            body_density: self.mass() / surfboard.volume(),
+            rider_offset: Vec3::Y * (surfboard.thickness / 2.0 + self.rider_height()),
+            ..default()
+        }
+    }
+
+    /// Board, floating body and hull to spawn alongside the board's mesh
+    pub fn bundle(self) -> (Surfboard, FloatingBody, BuoyancyHull) {
+        let surfboard = self.surfboard();
+        let mesh = create_surfboard_mesh(&surfboard);
+        let hull = BuoyancyHull::from_mesh(&mesh).expect("surfboard mesh is an indexed triangle list");
+        let floating_body = self.floating_body_from_mesh(&surfboard, &mesh);
+        (surfboard, floating_body, hull)
+    }
+}
@@ -0,0 +1,168 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::{hull_buoyancy::BuoyancyHull, surfboard::FloatingBody};
+
+/// Shape the buoyancy points fill, centred on the body origin
+#[derive(Debug, Clone)]
+enum BuoyancyShape {
+    Cuboid(Cuboid),
+    Capsule(Capsule3d), // Along the body Y axis, like the Bevy primitive
+    Sphere(Sphere),
+    Hull(BuoyancyHull),
+}
+
+impl BuoyancyShape {
+    fn bounds(&self) -> (Vec3, Vec3) {
+        match self {
+            Self::Cuboid(cuboid) => (-cuboid.half_size, cuboid.half_size),
+            Self::Capsule(capsule) => {
+                let half_size = Vec3::new(capsule.radius, capsule.half_length + capsule.radius, capsule.radius);
+                (-half_size, half_size)
+            }
+            Self::Sphere(sphere) => (Vec3::splat(-sphere.radius), Vec3::splat(sphere.radius)),
+            Self::Hull(hull) => (
+                hull.vertices.iter().copied().fold(Vec3::INFINITY, Vec3::min),
+                hull.vertices.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max),
+            ),
+        }
+    }
+
+    fn volume(&self) -> f32 {
+        match self {
+            Self::Cuboid(cuboid) => cuboid.volume(),
+            Self::Capsule(capsule) => capsule.volume(),
+            Self::Sphere(sphere) => sphere.volume(),
+            Self::Hull(hull) => hull.volume(),
+        }
+    }
+
+    fn contains(&self, point: Vec3) -> bool {
+        match self {
+            Self::Cuboid(cuboid) => point.abs().cmple(cuboid.half_size).all(),
+            Self::Capsule(capsule) => {
+                let axis = Vec3::Y * point.y.clamp(-capsule.half_length, capsule.half_length);
+                point.distance_squared(axis) <= capsule.radius * capsule.radius
+            }
+            Self::Sphere(sphere) => point.length_squared() <= sphere.radius * sphere.radius,
+            // Generalised winding number: the solid angle the hull subtends, which is 4π inside
+            Self::Hull(hull) => {
+                let solid_angle: f32 = hull
+                    .triangles
+                    .iter()
+                    .map(|&[a, b, c]| {
+                        let [a, b, c] = [a, b, c].map(|vertex| hull.vertices[vertex as usize] - point);
+                        let (la, lb, lc) = (a.length(), b.length(), c.length());
+                        let denominator = la * lb * lc + a.dot(b) * lc + a.dot(c) * lb + b.dot(c) * la;
+                        2.0 * a.dot(b.cross(c)).atan2(denominator)
+                    })
+                    .sum();
+                solid_angle > 2.0 * PI
+            }
+        }
+    }
+}
+
+/// Spreads buoyancy points through a shape on a grid, weighting each by the share of the shape's volume
+/// its grid cell holds, e.g. `BuoyancyPointsBuilder::sphere(Sphere::new(0.5)).with_spacing(0.2).build()`.
+#[derive(Debug, Clone)]
+pub struct BuoyancyPointsBuilder {
+    shape: BuoyancyShape,
+    spacing: f32,    // Largest distance between neighbouring points
+    subsamples: u32, // Per cell axis, for the share of each cell inside the shape
+}
+
+impl BuoyancyPointsBuilder {
+    fn new(shape: BuoyancyShape) -> Self {
+        Self {
+            shape,
+            spacing: 0.25,
+            subsamples: 4,
+        }
+    }
+
+    pub fn cuboid(cuboid: Cuboid) -> Self {
+        Self::new(BuoyancyShape::Cuboid(cuboid))
+    }
+
+    pub fn capsule(capsule: Capsule3d) -> Self {
+        Self::new(BuoyancyShape::Capsule(capsule))
+    }
+
+    pub fn sphere(sphere: Sphere) -> Self {
+        Self::new(BuoyancyShape::Sphere(sphere))
+    }
+
+    /// Fill the closed triangle mesh `mesh`, wound like `BuoyancyHull` expects
+    pub fn mesh(mesh: &Mesh) -> Option<Self> {
+        BuoyancyHull::from_mesh(mesh).map(|hull| Self::new(BuoyancyShape::Hull(hull)))
+    }
+
+    /// Sampling density: the largest distance between neighbouring points, 0.25 m by default
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        assert!(spacing > 0.0, "buoyancy point spacing must be positive, got {spacing}");
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sample points per cell axis when measuring how much of each cell is inside the shape
+    pub fn with_subsamples(mut self, subsamples: u32) -> Self {
+        self.subsamples = subsamples.max(1);
+        self
+    }
+
+    /// Points, the volume each stands for and the vertical extent of those volumes, with the volumes
+    /// summing to the shape's
+    pub fn points(&self) -> (Vec<Vec3>, Vec<f32>, f32) {
+        let (min, max) = self.shape.bounds();
+        let extent = (max - min).max(Vec3::ZERO);
+        // Cells fit the bounds exactly, so thin shapes get one layer of points their own thickness
+        let cells = (extent / self.spacing).ceil().max(Vec3::ONE).as_uvec3();
+        let cell = extent / cells.as_vec3();
+        let subsamples = self.subsamples;
+        let subsample_volume = cell.element_product() / subsamples.pow(3) as f32;
+
+        let mut points = Vec::new();
+        let mut volumes = Vec::new();
+        for x in 0..cells.x {
+            for y in 0..cells.y {
+                for z in 0..cells.z {
+                    let corner = min + UVec3::new(x, y, z).as_vec3() * cell;
+                    let (mut inside, mut centroid) = (0, Vec3::ZERO);
+                    for i in 0..subsamples.pow(3) {
+                        let offset = UVec3::new(i % subsamples, i / subsamples % subsamples, i / subsamples.pow(2));
+                        let sample = corner + (offset.as_vec3() + 0.5) / subsamples as f32 * cell;
+                        if self.shape.contains(sample) {
+                            inside += 1;
+                            centroid += sample;
+                        }
+                    }
+                    if inside > 0 {
+                        points.push(centroid / inside as f32);
+                        volumes.push(inside as f32 * subsample_volume);
+                    }
+                }
+            }
+        }
+
+        // Share out the exact volume in proportion to the sampled one
+        let sampled: f32 = volumes.iter().sum();
+        if sampled > 0.0 {
+            let scale = self.shape.volume() / sampled;
+            volumes.iter_mut().for_each(|volume| *volume *= scale);
+        }
+        (points, volumes, cell.y)
+    }
+
+    /// A `FloatingBody` on the generated points, with default densities and drag
+    pub fn build(&self) -> FloatingBody {
+        let (buoyancy_points, point_volumes, point_height) = self.points();
+        FloatingBody {
+            buoyancy_points,
+            point_volumes,
+            point_height,
+            ..default()
+        }
+    }
+}
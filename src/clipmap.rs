@@ -0,0 +1,233 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+use crate::water::WaterSurface;
+
+/// Infinite ocean surface made of nested square LOD levels (a geometry clipmap) that follows the camera.
+///
+/// Level 0 is the finest grid, every following level doubles the cell size and leaves a hole where
+/// the previous level sits. Each level snaps to twice its own cell size, so vertices only ever move by
+/// whole grid steps and the displaced surface does not swim as the camera moves.
+#[derive(Component, Debug, Clone)]
+pub struct WaterClipmap {
+    pub levels: usize,     // Number of LOD levels
+    pub resolution: usize, // Grid cells per side of every level, must be a multiple of 4
+    pub cell_size: f32,    // Cell size of the finest level in meters
+    centres: Vec<IVec2>,   // Snapped centre of every level, in cells of that level
+}
+
+impl Default for WaterClipmap {
+    fn default() -> Self {
+        Self::new(6, 64, 0.5)
+    }
+}
+
+impl WaterClipmap {
+    pub fn new(levels: usize, resolution: usize, cell_size: f32) -> Self {
+        assert!(levels > 0, "a clipmap needs at least one level");
+        assert!(
+            resolution >= 8 && resolution.is_multiple_of(4),
+            "clipmap resolution must be a multiple of 4, got {resolution}"
+        );
+
+        Self {
+            levels,
+            resolution,
+            cell_size,
+            centres: vec![IVec2::ZERO; levels],
+        }
+    }
+
+    pub fn vertices_per_level(&self) -> usize {
+        (self.resolution + 1) * (self.resolution + 1)
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.levels * self.vertices_per_level()
+    }
+
+    pub fn level_cell_size(&self, level: usize) -> f32 {
+        self.cell_size * (1 << level) as f32
+    }
+
+    /// Width of the whole clipmap, i.e. of the coarsest level
+    pub fn extent(&self) -> f32 {
+        self.level_cell_size(self.levels - 1) * self.resolution as f32
+    }
+
+    /// Centre of `level` in water-local XZ
+    pub fn level_centre(&self, level: usize) -> Vec2 {
+        self.centres[level].as_vec2() * self.level_cell_size(level)
+    }
+
+    /// Snap every level around the water-local `focus` point. Returns true if any level moved.
+    pub fn recentre(&mut self, focus: Vec2) -> bool {
+        let mut moved = false;
+        for level in 0..self.levels {
+            // Centres stay on even cells so the next finer level lands on whole cells of this one
+            let step = 2.0 * self.level_cell_size(level);
+            let centre = (focus / step).round().as_ivec2() * 2;
+            moved |= self.centres[level] != centre;
+            self.centres[level] = centre;
+        }
+        moved
+    }
+
+    /// Water-local base position of every vertex, level by level in row-major order
+    pub fn base_positions(&self, positions: &mut Vec<Vec3>) {
+        positions.clear();
+        let half = (self.resolution / 2) as i32;
+
+        for level in 0..self.levels {
+            let cell_size = self.level_cell_size(level);
+            let origin = self.centres[level] - IVec2::splat(half);
+            for z in 0..=self.resolution as i32 {
+                for x in 0..=self.resolution as i32 {
+                    let cell = origin + IVec2::new(x, z);
+                    positions.push(Vec3::new(cell.x as f32 * cell_size, 0.0, cell.y as f32 * cell_size));
+                }
+            }
+        }
+    }
+
+    /// Triangles of every level, skipping the cells covered by the next finer level
+    pub fn indices(&self) -> Vec<u32> {
+        let grid = self.resolution + 1;
+        let half = (self.resolution / 2) as i32;
+        let quarter = (self.resolution / 4) as i32;
+        let mut indices = Vec::new();
+
+        for level in 0..self.levels {
+            let origin = self.centres[level] - IVec2::splat(half);
+            // The finer level's footprint in this level's cells, empty for level 0
+            let hole = (level > 0).then(|| {
+                let centre = self.centres[level - 1] / 2;
+                (centre - IVec2::splat(quarter), centre + IVec2::splat(quarter))
+            });
+
+            for z in 0..self.resolution {
+                for x in 0..self.resolution {
+                    let cell = origin + IVec2::new(x as i32, z as i32);
+                    if let Some((min, max)) = hole
+                        && cell.cmpge(min).all()
+                        && cell.cmplt(max).all()
+                    {
+                        continue;
+                    }
+
+                    let idx = (level * grid * grid + z * grid + x) as u32;
+                    let grid = grid as u32;
+                    indices.extend_from_slice(&[idx, idx + grid, idx + 1, idx + 1, idx + grid, idx + grid + 1]);
+                }
+            }
+        }
+
+        indices
+    }
+
+    /// Vertices on the outer edge of every level but the coarsest that fall between two vertices of
+    /// the surrounding level, as `[vertex, edge start, edge end]`
+    pub fn seam_vertices(&self) -> Vec<[u32; 3]> {
+        let grid = self.resolution + 1;
+        let last = self.resolution;
+        let mut seams = Vec::new();
+
+        for level in 0..self.levels - 1 {
+            let index = |x: usize, z: usize| (level * grid * grid + z * grid + x) as u32;
+            for i in (1..last).step_by(2) {
+                seams.push([index(i, 0), index(i - 1, 0), index(i + 1, 0)]);
+                seams.push([index(i, last), index(i - 1, last), index(i + 1, last)]);
+                seams.push([index(0, i), index(0, i - 1), index(0, i + 1)]);
+                seams.push([index(last, i), index(last, i - 1), index(last, i + 1)]);
+            }
+        }
+
+        seams
+    }
+}
+
+/// Build the mesh for `clipmap` at its current centres, along with the base vertex positions
+pub fn create_clipmap_mesh(clipmap: &WaterClipmap) -> (Mesh, Vec<Vec3>) {
+    let mut base_positions = Vec::with_capacity(clipmap.vertex_count());
+    clipmap.base_positions(&mut base_positions);
+
+    let positions: Vec<[f32; 3]> = base_positions.iter().map(|pos| pos.to_array()).collect();
+    let normals = vec![[0.0, 1.0, 0.0]; positions.len()];
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, clipmap_uvs(clipmap, &base_positions));
+    mesh.insert_indices(Indices::U32(clipmap.indices()));
+
+    (mesh, base_positions)
+}
+
+/// Snap the displaced seam vertices onto the edges of the coarser level around them so the levels
+/// meet without cracks, and shade them as the coarser edge does by blending its normals and tangents
+pub fn stitch_seams(
+    seams: &[[u32; 3]],
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+) {
+    for &seam in seams {
+        let [vertex, start, end] = seam.map(|index| index as usize);
+        pos_data[vertex] = Vec3::from(pos_data[start]).midpoint(Vec3::from(pos_data[end])).to_array();
+        let normal = Vec3::from(normal_data[start]) + Vec3::from(normal_data[end]);
+        normal_data[vertex] = normal.normalize_or(Vec3::Y).to_array();
+        let tangent = Vec3::from_slice(&tangent_data[start]) + Vec3::from_slice(&tangent_data[end]);
+        tangent_data[vertex] = tangent.normalize_or(Vec3::X).extend(tangent_data[start][3]).to_array();
+    }
+}
+
+/// Recentre every clipmap around the active camera, rebuilding its base positions and triangles when
+/// a level snaps to a new position
+pub fn follow_camera_with_clipmap(
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut clipmaps: Query<(&mut WaterClipmap, &mut WaterSurface, &GlobalTransform, &Mesh3d)>,
+    mut base_positions: Local<Vec<Vec3>>,
+) {
+    let Some((_, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active) else {
+        return;
+    };
+
+    for (mut clipmap, mut surface, transform, mesh_3d) in clipmaps.iter_mut() {
+        let focus = transform.affine().inverse().transform_point3(camera_transform.translation());
+        if !clipmap.recentre(Vec2::new(focus.x, focus.z)) {
+            continue;
+        }
+
+        clipmap.base_positions(&mut base_positions);
+        surface.base_positions.set(&base_positions);
+        if let Some(mesh) = meshes.get_mut(&mesh_3d.0) {
+            // Positions are rewritten by `update_water_vertices`, the rest follows the new grid here
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, clipmap_uvs(&clipmap, &base_positions));
+            mesh.insert_indices(Indices::U32(clipmap.indices()));
+            if let Some(VertexAttributeValues::Float32x3(pos_data)) = mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
+                for (vertex, base_pos) in pos_data.iter_mut().zip(base_positions.iter()) {
+                    *vertex = base_pos.to_array();
+                }
+            }
+        }
+    }
+}
+
+/// UVs tile once per finest level so textures stay put while the grid moves
+fn clipmap_uvs(clipmap: &WaterClipmap, base_positions: &[Vec3]) -> Vec<[f32; 2]> {
+    let tile_size = clipmap.level_cell_size(0) * clipmap.resolution as f32;
+    base_positions
+        .iter()
+        .map(|pos| [pos.x / tile_size, pos.z / tile_size])
+        .collect()
+}
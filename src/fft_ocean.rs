@@ -0,0 +1,313 @@
+use std::{f32::consts::PI, fmt, sync::Arc};
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rustfft::{Fft, FftPlanner, num_complex::Complex32};
+
+use crate::water::GRAVITY;
+
+/// Inputs for a Tessendorf FFT ocean. Identical settings always produce an identical sea.
+#[derive(Debug, Clone, Copy)]
+pub struct FftOceanSettings {
+    pub resolution: usize,    // FFT grid cells per side, must be a power of two
+    pub patch_size: f32,      // World-space size of one (tiling) ocean patch in meters
+    pub wind_speed: f32,      // Wind speed in m/s
+    pub wind_direction: Vec2, // Direction the wind blows towards on the XZ plane
+    pub amplitude: f32,       // Phillips spectrum constant (dimensionless)
+    pub choppiness: f32,      // Horizontal displacement scale, lambda in Tessendorf's paper
+    pub seed: u64,            // Random seed for the initial spectrum
+}
+
+impl Default for FftOceanSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 64,
+            patch_size: 100.0,
+            wind_speed: 8.0,
+            wind_direction: Vec2::new(1.0, 0.1).normalize(),
+            amplitude: 0.0081,
+            choppiness: 1.0,
+            seed: 0,
+        }
+    }
+}
+
+/// Ocean surface synthesized from a statistical wave spectrum with an inverse FFT on the CPU.
+///
+/// Use it on a water entity instead of (or together with) [`WaterWaves`](crate::water::WaterWaves);
+/// the displacement field tiles every `patch_size` meters.
+#[derive(Component)]
+pub struct FftOcean {
+    settings: FftOceanSettings,
+    wave_vectors: Vec<Vec2>,
+    angular_frequencies: Vec<f32>,
+    h0: Vec<Complex32>,
+    h0_minus_k_conj: Vec<Complex32>, // conj(h0(-k)), kept separately so evolve() is a straight loop
+    height_field: Vec<Complex32>,
+    displacement_x_field: Vec<Complex32>,
+    displacement_z_field: Vec<Complex32>,
+    velocity_fields: [Vec<Complex32>; 3],
+    acceleration_fields: [Vec<Complex32>; 3],
+    transpose_scratch: Vec<Complex32>,
+    fft: Arc<dyn Fft<f32>>,
+    displacement: Vec<Vec3>,
+    velocity: Vec<Vec3>,
+    acceleration: Vec<Vec3>,
+}
+
+impl fmt::Debug for FftOcean {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FftOcean")
+            .field("settings", &self.settings)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for FftOcean {
+    fn default() -> Self {
+        Self::new(FftOceanSettings::default())
+    }
+}
+
+impl FftOcean {
+    pub fn new(settings: FftOceanSettings) -> Self {
+        let n = settings.resolution;
+        assert!(
+            n >= 2 && n.is_power_of_two(),
+            "FftOcean resolution must be a power of two, got {n}"
+        );
+
+        let cell_count = n * n;
+        let delta_k = 2.0 * PI / settings.patch_size;
+        let wind_direction = settings.wind_direction.normalize_or(Vec2::X);
+        let mut rng = ChaCha8Rng::seed_from_u64(settings.seed);
+
+        let mut wave_vectors = Vec::with_capacity(cell_count);
+        let mut angular_frequencies = Vec::with_capacity(cell_count);
+        let mut h0 = Vec::with_capacity(cell_count);
+
+        for z in 0..n {
+            for x in 0..n {
+                // Grid index 0 maps to the most negative wave number, -N/2 * dk
+                let k = Vec2::new(
+                    (x as f32 - (n / 2) as f32) * delta_k,
+                    (z as f32 - (n / 2) as f32) * delta_k,
+                );
+                let spectrum = phillips_spectrum(k, wind_direction, &settings);
+
+                // E|h0|^2 = P(k) dk^2 / 2, since h0(k) and h0(-k) both contribute to each mode
+                let (xi_r, xi_i) = gaussian_pair(&mut rng);
+                let scale = 0.5 * delta_k * spectrum.sqrt();
+
+                wave_vectors.push(k);
+                angular_frequencies.push((GRAVITY * k.length()).sqrt());
+                h0.push(Complex32::new(xi_r * scale, xi_i * scale));
+            }
+        }
+
+        let h0_minus_k_conj = (0..cell_count)
+            .map(|idx| {
+                let (x, z) = (idx % n, idx / n);
+                h0[((n - z) % n) * n + (n - x) % n].conj()
+            })
+            .collect();
+
+        let fft = FftPlanner::new().plan_fft_inverse(n);
+
+        let mut ocean = Self {
+            settings,
+            wave_vectors,
+            angular_frequencies,
+            h0,
+            h0_minus_k_conj,
+            height_field: vec![Complex32::default(); cell_count],
+            displacement_x_field: vec![Complex32::default(); cell_count],
+            displacement_z_field: vec![Complex32::default(); cell_count],
+            velocity_fields: std::array::from_fn(|_| vec![Complex32::default(); cell_count]),
+            acceleration_fields: std::array::from_fn(|_| vec![Complex32::default(); cell_count]),
+            transpose_scratch: vec![Complex32::default(); cell_count],
+            fft,
+            displacement: vec![Vec3::ZERO; cell_count],
+            velocity: vec![Vec3::ZERO; cell_count],
+            acceleration: vec![Vec3::ZERO; cell_count],
+        };
+        ocean.evolve(0.0);
+        ocean
+    }
+
+    pub fn settings(&self) -> &FftOceanSettings {
+        &self.settings
+    }
+
+    /// Advance the spectrum to `time` and rebuild the spatial displacement, velocity and acceleration fields
+    pub fn evolve(&mut self, time: f32) {
+        for idx in 0..self.h0.len() {
+            let omega = self.angular_frequencies[idx];
+            let (sin_wt, cos_wt) = (omega * time).sin_cos();
+            let rotation = Complex32::new(cos_wt, sin_wt);
+            let (forward, backward) = (self.h0[idx] * rotation, self.h0_minus_k_conj[idx] * rotation.conj());
+            let h = forward + backward;
+            // dh/dt = i ω (h0 e^(iωt) - conj(h0(-k)) e^(-iωt)) and d²h/dt² = -ω² h
+            let dh_dt = Complex32::new(0.0, omega) * (forward - backward);
+            let d2h_dt2 = -omega * omega * h;
+
+            // Choppy displacement D(k) = -i * k/|k| * h(k)
+            let k = self.wave_vectors[idx];
+            let k_hat = k.normalize_or_zero();
+            let chop_x = Complex32::new(0.0, -k_hat.x);
+            let chop_z = Complex32::new(0.0, -k_hat.y);
+            self.height_field[idx] = h;
+            self.displacement_x_field[idx] = chop_x * h;
+            self.displacement_z_field[idx] = chop_z * h;
+            for (fields, rate) in [(&mut self.velocity_fields, dh_dt), (&mut self.acceleration_fields, d2h_dt2)] {
+                fields[0][idx] = chop_x * rate;
+                fields[1][idx] = rate;
+                fields[2][idx] = chop_z * rate;
+            }
+        }
+
+        let n = self.settings.resolution;
+        inverse_fft_2d(&*self.fft, &mut self.height_field, &mut self.transpose_scratch, n);
+        inverse_fft_2d(&*self.fft, &mut self.displacement_x_field, &mut self.transpose_scratch, n);
+        inverse_fft_2d(&*self.fft, &mut self.displacement_z_field, &mut self.transpose_scratch, n);
+        for field in self.velocity_fields.iter_mut().chain(&mut self.acceleration_fields) {
+            inverse_fft_2d(&*self.fft, field, &mut self.transpose_scratch, n);
+        }
+
+        let choppiness = self.settings.choppiness;
+        let scale = Vec3::new(choppiness, 1.0, choppiness);
+        for z in 0..n {
+            for x in 0..n {
+                let idx = z * n + x;
+                // Undo the half-grid shift of the wave numbers: e^(-i*pi*(x+z)) = (-1)^(x+z)
+                let sign = if (x + z) % 2 == 0 { 1.0 } else { -1.0 };
+                self.displacement[idx] = Vec3::new(
+                    sign * choppiness * self.displacement_x_field[idx].re,
+                    sign * self.height_field[idx].re,
+                    sign * choppiness * self.displacement_z_field[idx].re,
+                );
+                let real = |fields: &[Vec<Complex32>; 3]| {
+                    Vec3::new(fields[0][idx].re, fields[1][idx].re, fields[2][idx].re)
+                };
+                self.velocity[idx] = sign * scale * real(&self.velocity_fields);
+                self.acceleration[idx] = sign * scale * real(&self.acceleration_fields);
+            }
+        }
+    }
+
+    /// Displacement (x, height, z) at a local-space position, bilinearly filtered and tiled
+    pub fn displacement_at(&self, position: Vec2) -> Vec3 {
+        self.filtered(&self.displacement, position)
+    }
+
+    /// Height at a local-space position (for physics, analogous to `get_wave_height`)
+    pub fn height_at(&self, position: Vec2) -> f32 {
+        self.displacement_at(position).y
+    }
+
+    /// Slope (dh/dx, dh/dz) of `height_at` at a local-space position
+    pub fn gradient_at(&self, position: Vec2) -> Vec2 {
+        let ([top_left, top_right, bottom_left, bottom_right], t) = self.cell(position);
+        let height = |idx: usize| self.displacement[idx].y;
+        // Derivatives of the bilinear patch, so the slope is exactly that of the filtered heights
+        let along_x = (height(top_right) - height(top_left)).lerp(height(bottom_right) - height(bottom_left), t.y);
+        let along_z = (height(bottom_left) - height(top_left)).lerp(height(bottom_right) - height(top_right), t.x);
+        Vec2::new(along_x, along_z) * (self.settings.resolution as f32 / self.settings.patch_size)
+    }
+
+    /// Velocity of the surface water at a local-space position, the rate of change of its displacement
+    pub fn velocity_at(&self, position: Vec2) -> Vec3 {
+        self.filtered(&self.velocity, position)
+    }
+
+    /// Acceleration of the surface water at a local-space position
+    pub fn acceleration_at(&self, position: Vec2) -> Vec3 {
+        self.filtered(&self.acceleration, position)
+    }
+
+    /// Wave number of the spectrum's peak, where the Phillips spectrum puts the most energy
+    pub fn peak_wave_number(&self) -> f32 {
+        let largest_wave = self.settings.wind_speed * self.settings.wind_speed / GRAVITY;
+        1.0 / (largest_wave * std::f32::consts::SQRT_2).max(f32::EPSILON)
+    }
+
+    /// `field` bilinearly filtered and tiled at a local-space position
+    fn filtered(&self, field: &[Vec3], position: Vec2) -> Vec3 {
+        let ([top_left, top_right, bottom_left, bottom_right], t) = self.cell(position);
+        let top = field[top_left].lerp(field[top_right], t.x);
+        let bottom = field[bottom_left].lerp(field[bottom_right], t.x);
+        top.lerp(bottom, t.y)
+    }
+
+    /// Grid indices of the tiled cell around a local-space position, and the position within it
+    fn cell(&self, position: Vec2) -> ([usize; 4], Vec2) {
+        let n = self.settings.resolution;
+        let grid = position / self.settings.patch_size * n as f32;
+        let cell = grid.floor();
+
+        let x0 = (cell.x as i64).rem_euclid(n as i64) as usize;
+        let z0 = (cell.y as i64).rem_euclid(n as i64) as usize;
+        let x1 = (x0 + 1) % n;
+        let z1 = (z0 + 1) % n;
+        ([z0 * n + x0, z0 * n + x1, z1 * n + x0, z1 * n + x1], grid - cell)
+    }
+}
+
+/// Phillips spectrum P(k) for a wave vector, in m^2 / (rad/m)^2
+fn phillips_spectrum(k: Vec2, wind_direction: Vec2, settings: &FftOceanSettings) -> f32 {
+    let k_length = k.length();
+    if k_length < 1e-6 {
+        return 0.0;
+    }
+
+    // Largest wave arising from a continuous wind of this speed
+    let largest_wave = settings.wind_speed * settings.wind_speed / GRAVITY;
+    let k_dot_w = (k / k_length).dot(wind_direction);
+    let k2 = k_length * k_length;
+
+    // Suppress waves much smaller than the largest one, as suggested by Tessendorf
+    let small_wave = largest_wave * 1e-3;
+
+    settings.amplitude / (2.0 * PI * k2 * k2)
+        * (-1.0 / (k2 * largest_wave * largest_wave)).exp()
+        * k_dot_w
+        * k_dot_w
+        * (-k2 * small_wave * small_wave).exp()
+}
+
+/// Two independent standard normal samples (Box-Muller)
+fn gaussian_pair(rng: &mut ChaCha8Rng) -> (f32, f32) {
+    let u1: f32 = rng.r#gen::<f32>().max(f32::MIN_POSITIVE);
+    let u2: f32 = rng.r#gen();
+    let radius = (-2.0 * u1.ln()).sqrt();
+    let (sin, cos) = (2.0 * PI * u2).sin_cos();
+    (radius * cos, radius * sin)
+}
+
+/// Unnormalized in-place 2D inverse FFT of a row-major n x n grid
+fn inverse_fft_2d(fft: &dyn Fft<f32>, data: &mut [Complex32], scratch: &mut [Complex32], n: usize) {
+    // Rows
+    fft.process(data);
+
+    // Columns, via transpose so each column is contiguous
+    transpose(data, scratch, n);
+    fft.process(scratch);
+    transpose(scratch, data, n);
+}
+
+fn transpose(src: &[Complex32], dst: &mut [Complex32], n: usize) {
+    for row in 0..n {
+        for col in 0..n {
+            dst[col * n + row] = src[row * n + col];
+        }
+    }
+}
+
+pub fn simulate_fft_ocean(time: Res<Time>, mut query: Query<&mut FftOcean>) {
+    let elapsed = time.elapsed_secs();
+
+    for mut ocean in query.iter_mut() {
+        ocean.evolve(elapsed);
+    }
+}
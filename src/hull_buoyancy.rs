@@ -0,0 +1,162 @@
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, VertexAttributeValues},
+};
+
+/// Closed triangle hull whose submerged volume floats a `FloatingBody`, in place of its buoyancy
+/// points. Triangles wind counter-clockwise seen from outside, like `create_surfboard_mesh`.
+#[derive(Component, Debug, Clone)]
+pub struct BuoyancyHull {
+    pub vertices: Vec<Vec3>, // Relative to the entity, like the buoyancy points
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// Piece of a hull triangle below the water surface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubmergedTriangle {
+    pub vertices: [Vec3; 3],  // World space, wound like the hull
+    pub hull_triangle: usize, // Index into `BuoyancyHull::triangles`
+}
+
+impl SubmergedTriangle {
+    /// Outward normal scaled by the area
+    pub fn area_vector(&self) -> Vec3 {
+        let [a, b, c] = self.vertices;
+        (b - a).cross(c - a) / 2.0
+    }
+
+    pub fn centroid(&self) -> Vec3 {
+        let [a, b, c] = self.vertices;
+        (a + b + c) / 3.0
+    }
+}
+
+/// The part of a hull below the water surface
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SubmergedVolume {
+    pub volume: f32,  // m³
+    pub centre: Vec3, // Centre of buoyancy in world space, the centroid of the submerged volume
+}
+
+impl BuoyancyHull {
+    /// Hull from the positions and triangle list of `mesh`
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            return None;
+        };
+        let indices: Vec<u32> = match mesh.indices()? {
+            Indices::U16(indices) => indices.iter().map(|&index| index as u32).collect(),
+            Indices::U32(indices) => indices.clone(),
+        };
+
+        Some(Self {
+            vertices: positions.iter().map(|&position| Vec3::from(position)).collect(),
+            triangles: indices.chunks_exact(3).map(|triangle| [triangle[0], triangle[1], triangle[2]]).collect(),
+        })
+    }
+
+    /// Volume enclosed by the hull
+    pub fn volume(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let [a, b, c] = [a, b, c].map(|vertex| self.vertices[vertex as usize]);
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+
+    /// Longest side of the hull's bounding box
+    pub fn length(&self) -> f32 {
+        let min = self.vertices.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+        let max = self.vertices.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max);
+        (max - min).max_element().max(0.0)
+    }
+
+    /// Surface area of the hull
+    pub fn area(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let [a, b, c] = [a, b, c].map(|vertex| self.vertices[vertex as usize]);
+                (b - a).cross(c - a).length() / 2.0
+            })
+            .sum()
+    }
+
+    /// Clip the hull, with its vertices at `world_vertices`, against the water surface at `water_heights`
+    /// above each vertex. Triangles crossing the surface are cut where the height difference interpolates
+    /// to zero. `clipped` receives the submerged pieces, grouped by hull triangle.
+    pub fn submerged(
+        &self,
+        world_vertices: &[Vec3],
+        water_heights: &[f32],
+        clipped: &mut Vec<SubmergedTriangle>,
+    ) -> SubmergedVolume {
+        clipped.clear();
+        // Where the hull crosses the surface, for a reference point on the water plane
+        let (mut waterline_sum, mut waterline_count) = (Vec3::ZERO, 0);
+
+        for (hull_triangle, triangle) in self.triangles.iter().enumerate() {
+            let corners = triangle.map(|vertex| {
+                let position = world_vertices[vertex as usize];
+                (position, position.y - water_heights[vertex as usize])
+            });
+
+            // Sutherland-Hodgman against "below the surface", which keeps the winding
+            let mut polygon = [Vec3::ZERO; 4];
+            let mut len = 0;
+            for (i, &(start, start_height)) in corners.iter().enumerate() {
+                let (end, end_height) = corners[(i + 1) % 3];
+                if start_height <= 0.0 {
+                    polygon[len] = start;
+                    len += 1;
+                }
+                if (start_height <= 0.0) != (end_height <= 0.0) {
+                    let crossing = start.lerp(end, start_height / (start_height - end_height));
+                    polygon[len] = crossing;
+                    len += 1;
+                    waterline_sum += crossing;
+                    waterline_count += 1;
+                }
+            }
+
+            for i in 1..len.saturating_sub(1) {
+                clipped.push(SubmergedTriangle {
+                    vertices: [polygon[0], polygon[i], polygon[i + 1]],
+                    hull_triangle,
+                });
+            }
+        }
+
+        if clipped.is_empty() {
+            return SubmergedVolume::default();
+        }
+
+        // The missing cap over the submerged part lies on the water surface, so tetrahedra from a point
+        // on the waterline to the cap have no volume and the clipped triangles alone enclose it. A fully
+        // submerged hull is closed already and works from any point.
+        let apex = if waterline_count > 0 {
+            waterline_sum / waterline_count as f32
+        } else {
+            clipped[0].vertices[0]
+        };
+
+        let mut volume = 0.0;
+        let mut moment = Vec3::ZERO;
+        for triangle in clipped.iter() {
+            let [a, b, c] = triangle.vertices;
+            let tetrahedron = (a - apex).dot((b - apex).cross(c - apex)) / 6.0;
+            volume += tetrahedron;
+            moment += tetrahedron * (a + b + c + apex) / 4.0;
+        }
+
+        if volume <= f32::EPSILON {
+            return SubmergedVolume::default();
+        }
+        SubmergedVolume {
+            volume,
+            centre: moment / volume,
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+
+use crate::{
+    hull_buoyancy::{BuoyancyHull, SubmergedTriangle},
+    rigid_body::Velocity,
+};
+
+/// Tunable coefficients for the forces the water puts on the submerged faces of a `BuoyancyHull` as they
+/// move through it, after Kerner's boat model. Speeds are relative to the water's orbital velocity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hydrodynamics {
+    pub pressure_drag_linear: f32,    // N/m² at the reference speed, on faces pushing into the water
+    pub pressure_drag_quadratic: f32, // N/m² at the reference speed, growing with its square
+    pub suction_drag_linear: f32,     // The same for faces pulling away from the water
+    pub suction_drag_quadratic: f32,
+    pub pressure_falloff: f32, // Exponent on the cosine between face normal and flow for pressure drag
+    pub suction_falloff: f32,
+    pub reference_speed: f32,     // m/s
+    pub kinematic_viscosity: f32, // m²/s, for the Reynolds number of the skin friction
+    pub slam_acceleration: f32,   // Rate of swept volume per hull area (m/s²) at which a face is stopped outright
+    pub slam_power: f32,          // Ramp of the slamming force up to `slam_acceleration`
+    pub planing_lift: f32,        // Normal force coefficient per radian of attack, on faces meeting the flow
+}
+
+impl Default for Hydrodynamics {
+    fn default() -> Self {
+        Self {
+            pressure_drag_linear: 10.0,
+            pressure_drag_quadratic: 10.0,
+            suction_drag_linear: 10.0,
+            suction_drag_quadratic: 10.0,
+            pressure_falloff: 0.5,
+            suction_falloff: 0.5,
+            reference_speed: 1.0,
+            kinematic_viscosity: 1.0e-6, // Water at 20°C
+            slam_acceleration: 50.0,
+            slam_power: 2.0,
+            planing_lift: std::f32::consts::FRAC_PI_2, // Flat planing plate at small angles
+        }
+    }
+}
+
+impl Hydrodynamics {
+    /// Force and torque about `centre_of_mass` on the `submerged` pieces of `hull`, clipped this tick.
+    /// `water_velocities` holds the orbital velocity of the water at each piece's centroid, and `length`
+    /// is the hull's length along the flow. Updates `swept_history`, the per-triangle sweep that slamming
+    /// compares against on the next tick.
+    pub fn hull_forces(
+        &self,
+        hull: &BuoyancyHull,
+        swept_history: &mut Vec<f32>,
+        submerged: &[SubmergedTriangle],
+        water_velocities: &[Vec3],
+        centre_of_mass: Vec3,
+        velocity: &Velocity,
+        mass: f32,
+        length: f32,
+        water_density: f32,
+        dt: f32,
+    ) -> (Vec3, Vec3) {
+        assert_eq!(submerged.len(), water_velocities.len());
+        let hull_area = hull.area().max(f32::EPSILON);
+        swept_history.resize(hull.triangles.len(), 0.0);
+        let mut force = Vec3::ZERO;
+        let mut torque = Vec3::ZERO;
+        let mut next_triangle = 0;
+        let mut water_velocities = water_velocities;
+
+        for pieces in submerged.chunk_by(|a, b| a.hull_triangle == b.hull_triangle) {
+            let hull_triangle = pieces[0].hull_triangle;
+            // Faces that left the water since the last tick sweep nothing
+            swept_history[next_triangle..hull_triangle].fill(0.0);
+            next_triangle = hull_triangle + 1;
+            let (piece_velocities, rest) = water_velocities.split_at(pieces.len());
+            water_velocities = rest;
+
+            let (mut area, mut swept, mut flow, mut centre) = (0.0, 0.0, Vec3::ZERO, Vec3::ZERO);
+            let mut normal = Vec3::ZERO;
+            for (piece, &water_velocity) in pieces.iter().zip(piece_velocities) {
+                let area_vector = piece.area_vector();
+                let piece_area = area_vector.length();
+                if piece_area <= f32::EPSILON {
+                    continue;
+                }
+                let piece_normal = area_vector / piece_area;
+                let point = piece.centroid();
+                let offset = point - centre_of_mass;
+                let relative = velocity.at_point(offset) - water_velocity;
+
+                let piece_force = self.face_force(relative, piece_normal, piece_area, length, water_density);
+                force += piece_force;
+                torque += offset.cross(piece_force);
+
+                area += piece_area;
+                swept += piece_area * relative.length();
+                flow += piece_area * relative;
+                centre += piece_area * point;
+                normal = piece_normal;
+            }
+            if area <= f32::EPSILON {
+                continue;
+            }
+
+            // Slamming: the face sweeps water aside faster than it did last tick
+            let previous = std::mem::replace(&mut swept_history[hull_triangle], swept);
+            let flow = flow / area;
+            let cos_theta = flow.normalize_or_zero().dot(normal);
+            let rate = (swept - previous) / (hull_area * dt);
+            if cos_theta > 0.0 && rate > 0.0 && self.slam_acceleration > 0.0 {
+                // The face's share of the body's momentum, stopped over one tick at full strength
+                let stop = mass * flow.length() * (2.0 * area / hull_area) / dt;
+                let strength = (rate / self.slam_acceleration).min(1.0).powf(self.slam_power);
+                let slam = -normal * strength * cos_theta * stop;
+                force += slam;
+                torque += (centre / area - centre_of_mass).cross(slam);
+            }
+        }
+        swept_history[next_triangle..].fill(0.0);
+
+        (force, torque)
+    }
+
+    /// Pressure drag, skin friction and planing lift on a face of `area` and outward `normal` moving at
+    /// `relative` velocity through the water
+    fn face_force(&self, relative: Vec3, normal: Vec3, area: f32, length: f32, water_density: f32) -> Vec3 {
+        let speed = relative.length();
+        if speed <= f32::EPSILON {
+            return Vec3::ZERO;
+        }
+        let direction = relative / speed;
+        let cos_theta = direction.dot(normal);
+        let reference = speed / self.reference_speed;
+
+        // Pressure on faces pushing into the water, suction behind faces pulling away from it
+        let mut force = if cos_theta > 0.0 {
+            let drag = self.pressure_drag_linear * reference + self.pressure_drag_quadratic * reference * reference;
+            -normal * drag * area * cos_theta.powf(self.pressure_falloff)
+        } else {
+            let drag = self.suction_drag_linear * reference + self.suction_drag_quadratic * reference * reference;
+            normal * drag * area * (-cos_theta).powf(self.suction_falloff)
+        };
+
+        // Skin friction along the face, with the ITTC 1957 line for the friction coefficient
+        let tangential = relative - normal * relative.dot(normal);
+        let reynolds = (speed * length / self.kinematic_viscosity).max(1.0e4);
+        let friction = 0.075 / (reynolds.log10() - 2.0).powi(2);
+        force -= tangential.normalize_or_zero() * 0.5 * water_density * friction * area * speed * speed;
+
+        // Planing: dynamic pressure on faces meeting the flow at an angle of attack. Like the pressure drag it
+        // pushes along the normal, so it lifts a board skimming nose up and never feeds energy in.
+        if cos_theta > 0.0 {
+            // Linear in the angle of attack while it is small, falling away again as the face turns square on
+            let attack = cos_theta * (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+            force -= normal * 0.5 * water_density * speed * speed * self.planing_lift * attack * area;
+        }
+
+        force
+    }
+}
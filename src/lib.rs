@@ -0,0 +1,17 @@
+pub mod batch_query;
+pub mod board_presets;
+pub mod buoyancy_points;
+pub mod clipmap;
+pub mod fft_ocean;
+pub mod hull_buoyancy;
+pub mod hydrodynamics;
+pub mod rigid_body;
+pub mod simd;
+pub mod spectrum;
+pub mod spreading;
+pub mod surfboard;
+pub mod surfboard_shape;
+pub mod water;
+pub mod water_material;
+pub mod water_query;
+pub mod water_tiles;
@@ -1,7 +1,6 @@
 use bevy::prelude::*;
 
-mod water;
-use water::WaterPlugin;
+use isosurf::water::WaterPlugin;
 
 fn main() -> AppExit {
     App::new()
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+const GYROSCOPIC_ITERATIONS: usize = 4; // Newton steps per tick, plenty at the spin rates boards reach
+
+/// Mass and principal moments of inertia of a body about its centre of mass, in body axes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassProperties {
+    pub mass: f32,     // kg
+    pub centre: Vec3,  // Centre of mass relative to the body origin, in body axes
+    pub inertia: Vec3, // kg m² about axes through the centre of mass along the body X, Y and Z axes
+}
+
+impl MassProperties {
+    /// Solid box of uniform `density` with full edge lengths `size` along the body axes
+    pub fn cuboid(density: f32, size: Vec3) -> Self {
+        let mass = density * size.x * size.y * size.z;
+        let squared = size * size;
+        Self {
+            mass,
+            centre: Vec3::ZERO,
+            inertia: Vec3::new(squared.y + squared.z, squared.x + squared.z, squared.x + squared.y) * (mass / 12.0),
+        }
+    }
+
+    /// Body of uniform `density` made of small cubes of `volumes` centred on `points`. Products of inertia
+    /// are left out, so the points should be symmetric about the body axes through their centre of mass.
+    pub fn from_points(density: f32, points: &[Vec3], volumes: &[f32]) -> Self {
+        assert_eq!(points.len(), volumes.len(), "one volume per point");
+        let volume: f32 = volumes.iter().sum();
+        let first_moment: Vec3 = points.iter().zip(volumes).map(|(point, &volume)| *point * volume).sum();
+        let centre = if volume > 0.0 { first_moment / volume } else { Vec3::ZERO };
+
+        let mut inertia = Vec3::ZERO;
+        for (point, &volume) in points.iter().zip(volumes) {
+            let point_mass = density * volume;
+            // Each cube's own inertia keeps a single point from having none
+            let own = point_mass * volume.cbrt().powi(2) / 6.0;
+            inertia += about_axes(*point - centre) * point_mass + own;
+        }
+        Self {
+            mass: density * volume,
+            centre,
+            inertia,
+        }
+    }
+
+    /// Solid of uniform `density` bounded by the closed, outward-wound `triangles`. Products of inertia are
+    /// left out, so the solid should be symmetric about the body axes through its centre of mass.
+    pub fn from_triangles(density: f32, positions: &[Vec3], triangles: &[[u32; 3]]) -> Self {
+        let mut volume = 0.0;
+        let mut first_moment = Vec3::ZERO;  // ∫ x, ∫ y, ∫ z over the solid
+        let mut second_moment = Vec3::ZERO; // ∫ x², ∫ y², ∫ z²
+        for &[a, b, c] in triangles {
+            let [a, b, c] = [a, b, c].map(|vertex| positions[vertex as usize]);
+            // Tetrahedron from the origin, signed so the outside cancels
+            let determinant = a.dot(b.cross(c));
+            volume += determinant / 6.0;
+            first_moment += determinant / 24.0 * (a + b + c);
+            second_moment += determinant / 60.0 * (a * a + b * b + c * c + a * b + a * c + b * c);
+        }
+        let centre = if volume > 0.0 { first_moment / volume } else { Vec3::ZERO };
+        // Parallel axis theorem, moving the moments from the origin to the centre of mass
+        let moment = (second_moment - volume * centre * centre) * density;
+        Self {
+            mass: density * volume,
+            centre,
+            inertia: Vec3::new(moment.y + moment.z, moment.x + moment.z, moment.x + moment.y),
+        }
+    }
+
+    /// The body with a point `mass` at `position` from the body origin added, moving the centre of mass
+    /// towards it. Products of inertia are left out, so `position` should lie on a body axis through it.
+    pub fn with_point_mass(self, mass: f32, position: Vec3) -> Self {
+        if mass <= 0.0 {
+            return self;
+        }
+        let total = self.mass + mass;
+        let centre = (self.centre * self.mass + position * mass) / total;
+        // Parallel axis theorem for both parts about the shared centre of mass
+        let shifted = self.inertia + about_axes(self.centre - centre) * self.mass;
+        Self {
+            mass: total,
+            centre,
+            inertia: shifted + about_axes(position - centre) * mass,
+        }
+    }
+
+    /// Inertia tensor in world axes for a body turned by `rotation`
+    pub fn world_inertia(&self, rotation: Quat) -> Mat3 {
+        let rotation = Mat3::from_quat(rotation);
+        rotation * Mat3::from_diagonal(self.inertia) * rotation.transpose()
+    }
+}
+
+/// Linear velocity of the centre of mass and angular velocity, both in world space
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Velocity {
+    pub linear: Vec3,  // m/s
+    pub angular: Vec3, // rad/s, axis times rate
+}
+
+impl Velocity {
+    /// Velocity of the body point at world-space `offset` from the centre of mass
+    pub fn at_point(&self, offset: Vec3) -> Vec3 {
+        self.linear + self.angular.cross(offset)
+    }
+
+    /// Advance the velocities and then `transform` by `dt` under the world-space `force` and `torque`
+    /// about the centre of mass (semi-implicit Euler). The body turns about its centre of mass, `mass.centre`
+    /// from `transform.translation`.
+    pub fn integrate(
+        &mut self,
+        transform: &mut Transform,
+        mass: &MassProperties,
+        force: Vec3,
+        torque: Vec3,
+        dt: f32,
+    ) {
+        self.linear += force / mass.mass * dt;
+
+        // Euler's equations by the implicit midpoint rule, I (ω' - ω) + dt ω̄ × I ω̄ = 0 with ω̄ the mean of
+        // the two, solved in body axes by a few Newton steps for ω̄. It keeps both the kinetic energy and
+        // the size of the angular momentum, where explicit steps gain energy and blow up on long, thin
+        // bodies spinning fast and implicit Euler bleeds the spin away.
+        let inertia = Mat3::from_diagonal(mass.inertia);
+        let start = transform.rotation.inverse() * self.angular;
+        let mut mean = start;
+        for _ in 0..GYROSCOPIC_ITERATIONS {
+            let momentum = inertia * mean;
+            let residual = 2.0 * (momentum - inertia * start) + mean.cross(momentum) * dt;
+            let jacobian = 2.0 * inertia + (skew(mean) * inertia - skew(momentum)) * dt;
+            mean -= jacobian.inverse() * residual;
+        }
+        self.angular = transform.rotation * (2.0 * mean - start);
+        self.angular += mass.world_inertia(transform.rotation).inverse() * torque * dt;
+
+        let centre = transform.translation + transform.rotation * mass.centre + self.linear * dt;
+        transform.rotation = (Quat::from_scaled_axis(self.angular * dt) * transform.rotation).normalize();
+        transform.translation = centre - transform.rotation * mass.centre;
+    }
+}
+
+/// Squared distances of `offset` from the X, Y and Z axes
+fn about_axes(offset: Vec3) -> Vec3 {
+    let squared = offset * offset;
+    Vec3::new(squared.y + squared.z, squared.x + squared.z, squared.x + squared.y)
+}
+
+/// Matrix of the cross product `vector × v`
+fn skew(vector: Vec3) -> Mat3 {
+    Mat3::from_cols(
+        Vec3::new(0.0, vector.z, -vector.y),
+        Vec3::new(-vector.z, 0.0, vector.x),
+        Vec3::new(vector.y, -vector.x, 0.0),
+    )
+}
@@ -0,0 +1,41 @@
+#import bevy_pbr::{
+    mesh_functions,
+    forward_io::{Vertex, VertexOutput},
+    view_transformations::position_world_to_clip,
+}
+#import isosurf::water_waves::{water_surface, water_surface_normal}
+
+// Displaces the flat water grid by the Gerstner sum in `water_waves`, the fragment stage is the
+// regular StandardMaterial one
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let grid_position = vertex.position.xz;
+    let surface = water_surface(grid_position);
+    let position = vec3<f32>(grid_position.x, 0.0, grid_position.y) + surface.displacement;
+
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(water_surface_normal(surface), vertex.instance_index);
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+
+#ifdef VERTEX_TANGENTS
+    // UVs grow along +x and +z, so the bitangent needs flipping like the CPU path does
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vec4<f32>(normalize(surface.tangent_x), -1.0),
+        vertex.instance_index
+    );
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+    return out;
+}
@@ -0,0 +1,64 @@
+#define_import_path isosurf::water_waves
+
+// Must match MAX_GPU_WAVES in water_material.rs
+const MAX_WAVES: u32 = 64u;
+
+// Mirrors `GpuWave`
+struct Wave {
+    direction: vec2<f32>,
+    amplitude: f32,
+    steepness: f32,
+    wave_number: f32,
+    speed: f32,
+    phase: f32,
+    _padding: f32,
+}
+
+// Mirrors `GpuWaves`
+struct WaterWaves {
+    waves: array<Wave, MAX_WAVES>,
+    wave_count: u32,
+    time: f32,
+}
+
+// Displacement and partial derivatives of the surface point that started at a grid position
+struct GerstnerSurface {
+    displacement: vec3<f32>,
+    tangent_x: vec3<f32>, // dP/dx
+    tangent_z: vec3<f32>, // dP/dz
+}
+
+@group(2) @binding(100) var<uniform> water_waves: WaterWaves;
+
+// Same terms as `displace_water_surface`, with phase = k (d . p) - w t + phi
+fn add_gerstner_wave(surface: GerstnerSurface, grid_position: vec2<f32>, wave: Wave, time: f32) -> GerstnerSurface {
+    let phase = wave.wave_number * dot(grid_position, wave.direction) - (wave.speed * time - wave.phase);
+    let sin_phase = sin(phase);
+    let cos_phase = cos(phase);
+    let direction = vec3<f32>(wave.direction.x, 0.0, wave.direction.y);
+    let q_a = wave.steepness * wave.amplitude;
+
+    // d/dp of the displacement: horizontal -Q*A*k*sin * d d^T, vertical A*k*cos * d
+    let q_a_k_sin = q_a * wave.wave_number * sin_phase;
+    let a_k_cos = wave.amplitude * wave.wave_number * cos_phase;
+
+    var out = surface;
+    out.displacement += direction * (q_a * cos_phase) + vec3<f32>(0.0, wave.amplitude * sin_phase, 0.0);
+    out.tangent_x += vec3<f32>(0.0, a_k_cos, 0.0) * wave.direction.x - direction * (q_a_k_sin * wave.direction.x);
+    out.tangent_z += vec3<f32>(0.0, a_k_cos, 0.0) * wave.direction.y - direction * (q_a_k_sin * wave.direction.y);
+    return out;
+}
+
+// Sum every wave in the uniform at `grid_position`
+fn water_surface(grid_position: vec2<f32>) -> GerstnerSurface {
+    var surface = GerstnerSurface(vec3<f32>(0.0), vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, 1.0));
+    let wave_count = min(water_waves.wave_count, MAX_WAVES);
+    for (var i = 0u; i < wave_count; i += 1u) {
+        surface = add_gerstner_wave(surface, grid_position, water_waves.waves[i], water_waves.time);
+    }
+    return surface;
+}
+
+fn water_surface_normal(surface: GerstnerSurface) -> vec3<f32> {
+    return normalize(cross(surface.tangent_z, surface.tangent_x));
+}
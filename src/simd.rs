@@ -0,0 +1,439 @@
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+use wide::{CmpGt, CmpLt, f32x4, f32x8};
+
+/// Widest lane count any `LaneWidth` uses. Splitting work on multiples of it keeps every lane
+/// width processing the same chunks.
+pub const MAX_LANES: usize = 16;
+
+/// Lane widths the wave kernels can run at, see `lane_dispatch!`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaneWidth {
+    X4,
+    X8,
+    X16,
+}
+
+impl LaneWidth {
+    pub const ALL: [LaneWidth; 3] = [LaneWidth::X4, LaneWidth::X8, LaneWidth::X16];
+
+    pub fn lanes(self) -> usize {
+        match self {
+            LaneWidth::X4 => 4,
+            LaneWidth::X8 => 8,
+            LaneWidth::X16 => 16,
+        }
+    }
+
+    /// Widest lane width the target features this crate is compiled with have registers for. `wide` picks its
+    /// instructions at compile time, so this stays `X4` unless the build enables AVX2 and FMA, e.g. with
+    /// `RUSTFLAGS="-C target-cpu=native"`.
+    pub fn detect() -> Self {
+        if cfg!(target_feature = "avx512f") {
+            LaneWidth::X16
+        } else if cfg!(all(target_feature = "avx2", target_feature = "fma")) {
+            LaneWidth::X8
+        } else {
+            LaneWidth::X4
+        }
+    }
+}
+
+/// A SIMD vector of f32 lanes that the wave kernels are written against
+pub trait SimdF32:
+    Copy
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const LANES: usize;
+
+    fn splat(value: f32) -> Self;
+    /// Load up to `LANES` values, repeating the last one into missing lanes
+    fn load(values: &[f32]) -> Self;
+    /// Load the first `LANES` values, which have to be there
+    fn from_slice(values: &[f32]) -> Self;
+    /// Store the first `out.len()` lanes
+    fn store(self, out: &mut [f32]);
+    fn sin_cos(self) -> (Self, Self);
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    /// Round to the nearest integer
+    fn round(self) -> Self;
+    fn floor(self) -> Self;
+    /// All-ones lanes where `self < rhs`
+    fn lt(self, rhs: Self) -> Self;
+    /// All-ones lanes where `self > rhs`
+    fn gt(self, rhs: Self) -> Self;
+    /// Lanes of `t` where `self` is set, `f` elsewhere
+    fn select(self, t: Self, f: Self) -> Self;
+    /// True if every lane of the mask is set
+    fn all(self) -> bool;
+}
+
+macro_rules! impl_simd_f32 {
+    ($simd:ty, $lanes:expr) => {
+        impl SimdF32 for $simd {
+            const LANES: usize = $lanes;
+
+            #[inline(always)]
+            fn splat(value: f32) -> Self {
+                <$simd>::splat(value)
+            }
+
+            #[inline(always)]
+            fn load(values: &[f32]) -> Self {
+                let mut lanes = [values.last().copied().unwrap_or(0.0); $lanes];
+                let count = values.len().min($lanes);
+                lanes[..count].copy_from_slice(&values[..count]);
+                <$simd>::new(lanes)
+            }
+
+            #[inline(always)]
+            fn from_slice(values: &[f32]) -> Self {
+                <$simd>::new(values[..$lanes].try_into().unwrap())
+            }
+
+            #[inline(always)]
+            fn store(self, out: &mut [f32]) {
+                let count = out.len().min($lanes);
+                out[..count].copy_from_slice(&self.to_array()[..count]);
+            }
+
+            #[inline(always)]
+            fn sin_cos(self) -> (Self, Self) {
+                <$simd>::sin_cos(self)
+            }
+
+            #[inline(always)]
+            fn abs(self) -> Self {
+                <$simd>::abs(self)
+            }
+
+            #[inline(always)]
+            fn sqrt(self) -> Self {
+                <$simd>::sqrt(self)
+            }
+
+            #[inline(always)]
+            fn round(self) -> Self {
+                <$simd>::round(self)
+            }
+
+            #[inline(always)]
+            fn floor(self) -> Self {
+                <$simd>::floor(self)
+            }
+
+            #[inline(always)]
+            fn lt(self, rhs: Self) -> Self {
+                self.cmp_lt(rhs)
+            }
+
+            #[inline(always)]
+            fn gt(self, rhs: Self) -> Self {
+                self.cmp_gt(rhs)
+            }
+
+            #[inline(always)]
+            fn select(self, t: Self, f: Self) -> Self {
+                self.blend(t, f)
+            }
+
+            #[inline(always)]
+            fn all(self) -> bool {
+                <$simd>::all(self)
+            }
+        }
+    };
+}
+
+impl_simd_f32!(f32x4, 4);
+impl_simd_f32!(f32x8, 8);
+
+/// A single lane, so scalar code can share the generic kernels. Masks are all-ones or all-zeros bits.
+impl SimdF32 for f32 {
+    const LANES: usize = 1;
+
+    #[inline(always)]
+    fn splat(value: f32) -> Self {
+        value
+    }
+
+    #[inline(always)]
+    fn load(values: &[f32]) -> Self {
+        values.first().copied().unwrap_or(0.0)
+    }
+
+    #[inline(always)]
+    fn from_slice(values: &[f32]) -> Self {
+        values[0]
+    }
+
+    #[inline(always)]
+    fn store(self, out: &mut [f32]) {
+        if let Some(out) = out.first_mut() {
+            *out = self;
+        }
+    }
+
+    #[inline(always)]
+    fn sin_cos(self) -> (Self, Self) {
+        f32::sin_cos(self)
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    #[inline(always)]
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    #[inline(always)]
+    fn round(self) -> Self {
+        // Ties to even, like the vector types
+        f32::round_ties_even(self)
+    }
+
+    #[inline(always)]
+    fn floor(self) -> Self {
+        f32::floor(self)
+    }
+
+    #[inline(always)]
+    fn lt(self, rhs: Self) -> Self {
+        f32::from_bits(if self < rhs { u32::MAX } else { 0 })
+    }
+
+    #[inline(always)]
+    fn gt(self, rhs: Self) -> Self {
+        f32::from_bits(if self > rhs { u32::MAX } else { 0 })
+    }
+
+    #[inline(always)]
+    fn select(self, t: Self, f: Self) -> Self {
+        if self.to_bits() != 0 { t } else { f }
+    }
+
+    #[inline(always)]
+    fn all(self) -> bool {
+        self.to_bits() != 0
+    }
+}
+
+/// 16 lanes as two `f32x8` halves, since `wide` stops at 8. Each half is still a 256-bit operation
+/// even on AVX-512 targets; the gain is two independent dependency chains per kernel iteration.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct f32x16([f32x8; 2]);
+
+impl f32x16 {
+    #[inline(always)]
+    fn map(self, f: impl Fn(f32x8) -> f32x8) -> Self {
+        Self([f(self.0[0]), f(self.0[1])])
+    }
+
+    #[inline(always)]
+    fn zip(self, rhs: Self, f: impl Fn(f32x8, f32x8) -> f32x8) -> Self {
+        Self([f(self.0[0], rhs.0[0]), f(self.0[1], rhs.0[1])])
+    }
+}
+
+macro_rules! impl_f32x16_op {
+    ($op:ident, $method:ident) => {
+        impl $op for f32x16 {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $method(self, rhs: Self) -> Self {
+                self.zip(rhs, $op::$method)
+            }
+        }
+    };
+}
+
+impl_f32x16_op!(Add, add);
+impl_f32x16_op!(Sub, sub);
+impl_f32x16_op!(Mul, mul);
+impl_f32x16_op!(Div, div);
+
+impl AddAssign for f32x16 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for f32x16 {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl SimdF32 for f32x16 {
+    const LANES: usize = 16;
+
+    #[inline(always)]
+    fn splat(value: f32) -> Self {
+        Self([f32x8::splat(value); 2])
+    }
+
+    #[inline(always)]
+    fn load(values: &[f32]) -> Self {
+        let (low, high) = values.split_at(values.len().min(8));
+        // An empty high half repeats the last value of the low one, as `load` promises
+        let high = if high.is_empty() { &low[low.len().saturating_sub(1)..] } else { high };
+        Self([f32x8::load(low), f32x8::load(high)])
+    }
+
+    #[inline(always)]
+    fn from_slice(values: &[f32]) -> Self {
+        Self([f32x8::from_slice(&values[..8]), f32x8::from_slice(&values[8..16])])
+    }
+
+    #[inline(always)]
+    fn store(self, out: &mut [f32]) {
+        let (low, high) = out.split_at_mut(out.len().min(8));
+        self.0[0].store(low);
+        self.0[1].store(high);
+    }
+
+    #[inline(always)]
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin_low, cos_low) = self.0[0].sin_cos();
+        let (sin_high, cos_high) = self.0[1].sin_cos();
+        (Self([sin_low, sin_high]), Self([cos_low, cos_high]))
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Self {
+        self.map(f32x8::abs)
+    }
+
+    #[inline(always)]
+    fn sqrt(self) -> Self {
+        self.map(f32x8::sqrt)
+    }
+
+    #[inline(always)]
+    fn round(self) -> Self {
+        self.map(f32x8::round)
+    }
+
+    #[inline(always)]
+    fn floor(self) -> Self {
+        self.map(f32x8::floor)
+    }
+
+    #[inline(always)]
+    fn lt(self, rhs: Self) -> Self {
+        self.zip(rhs, <f32x8 as SimdF32>::lt)
+    }
+
+    #[inline(always)]
+    fn gt(self, rhs: Self) -> Self {
+        self.zip(rhs, <f32x8 as SimdF32>::gt)
+    }
+
+    #[inline(always)]
+    fn select(self, t: Self, f: Self) -> Self {
+        Self([self.0[0].select(t.0[0], f.0[0]), self.0[1].select(t.0[1], f.0[1])])
+    }
+
+    #[inline(always)]
+    fn all(self) -> bool {
+        self.0[0].all() && self.0[1].all()
+    }
+}
+
+/// How exactly the wave kernels evaluate the sine and cosine of their phases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SinCosPrecision {
+    #[default]
+    Exact, // `SimdF32::sin_cos`, good to a few ulp
+    Fast, // Within 1e-4
+    Fastest, // Within 1e-3, with a cubic instead of a quintic for sin r
+}
+
+impl SinCosPrecision {
+    pub const ALL: [SinCosPrecision; 3] = [SinCosPrecision::Exact, SinCosPrecision::Fast, SinCosPrecision::Fastest];
+
+    /// Largest absolute error of `sin_cos` at this precision, for phases up to about 1e4 radians
+    pub fn max_error(self) -> f32 {
+        match self {
+            SinCosPrecision::Exact => 1e-6,
+            SinCosPrecision::Fast => 1e-4,
+            SinCosPrecision::Fastest => 1e-3,
+        }
+    }
+}
+
+/// Sine and cosine of every lane at `precision`
+///
+/// The approximations reduce each lane to r in [-π/4, π/4] around the nearest multiple q of π/2,
+/// evaluate near-minimax polynomials for sin r and cos r, then swap and negate them for the
+/// quadrant q mod 4. Both come out of one reduction, which is most of the saving over `sin_cos`.
+#[inline(always)]
+pub fn sin_cos<S: SimdF32>(x: S, precision: SinCosPrecision) -> (S, S) {
+    // π/2 split so that q * PI_2_HI is exact for any q an f32 phase can reach in practice
+    const PI_2_HI: f32 = 1.5703125;
+    const PI_2_LO: f32 = 4.838_268e-4;
+
+    if precision == SinCosPrecision::Exact {
+        return x.sin_cos();
+    }
+
+    let quadrant = (x * S::splat(std::f32::consts::FRAC_2_PI)).round();
+    let r = (x - quadrant * S::splat(PI_2_HI)) - quadrant * S::splat(PI_2_LO);
+    let r2 = r * r;
+
+    let sin_r = if precision == SinCosPrecision::Fastest {
+        r + r * r2 * S::splat(-0.162_259_13)
+    } else {
+        r + r * r2 * (S::splat(-0.166_666_67) + r2 * S::splat(0.008_222_716))
+    };
+    let cos_r = S::splat(1.0) + r2 * (S::splat(-0.5) + r2 * S::splat(0.040_908_444));
+
+    // q mod 4: 0 -> (sin r, cos r), 1 -> (cos r, -sin r), 2 -> (-sin r, -cos r), 3 -> (-cos r, sin r)
+    let quadrant = quadrant - S::splat(4.0) * (quadrant * S::splat(0.25)).floor();
+    let odd = (quadrant - S::splat(2.0) * (quadrant * S::splat(0.5)).floor()).gt(S::splat(0.5));
+    let sin_negative = quadrant.gt(S::splat(1.5));
+    let cos_negative = (quadrant - S::splat(1.5)).abs().lt(S::splat(1.0));
+
+    let sin = odd.select(cos_r, sin_r);
+    let cos = odd.select(sin_r, cos_r);
+    let zero = S::splat(0.0);
+    (sin_negative.select(zero - sin, sin), cos_negative.select(zero - cos, cos))
+}
+
+/// Defines `$name(lane_width, args..)`, which runs the generic `$kernel::<S>(args..)` at the given
+/// lane width. There is no runtime feature detection: `wide` chooses its instructions from the
+/// compile-time target, so the wider widths only use AVX registers when the crate is built with
+/// them enabled, e.g. `RUSTFLAGS="-C target-cpu=native"`. Otherwise they run as several SSE halves.
+macro_rules! lane_dispatch {
+    (
+        $(#[$meta:meta])*
+        $vis:vis fn $name:ident => $kernel:ident($($arg:ident: $ty:ty),* $(,)?)
+    ) => {
+        $(#[$meta])*
+        $vis fn $name(lane_width: $crate::simd::LaneWidth, $($arg: $ty),*) {
+            use $crate::simd::{LaneWidth, f32x16};
+            use wide::{f32x4, f32x8};
+
+            match lane_width {
+                LaneWidth::X4 => $kernel::<f32x4>($($arg),*),
+                LaneWidth::X8 => $kernel::<f32x8>($($arg),*),
+                LaneWidth::X16 => $kernel::<f32x16>($($arg),*),
+            }
+        }
+    };
+}
+
+pub(crate) use lane_dispatch;
@@ -0,0 +1,231 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    spreading::DirectionalSpreading,
+    water::{GRAVITY, WaveParameters, clamp_wave_steepness},
+};
+
+/// Parameters shared by the fetch-limited JONSWAP and TMA spectra
+#[derive(Debug, Clone, Copy)]
+pub struct JonswapParameters {
+    pub wind_speed: f32,          // Wind speed 10m above the surface in m/s
+    pub fetch: f32,               // Distance over which the wind has blown in meters
+    pub gamma: f32,               // Peak enhancement factor (3.3 for the mean JONSWAP sea)
+    pub peak_period: Option<f32>, // Overrides the fetch-derived peak period in seconds
+}
+
+impl Default for JonswapParameters {
+    fn default() -> Self {
+        Self {
+            wind_speed: 10.0,
+            fetch: 100_000.0,
+            gamma: 3.3,
+            peak_period: None,
+        }
+    }
+}
+
+/// Omnidirectional frequency spectrum S(ω) describing a sea state
+#[derive(Debug, Clone, Copy)]
+pub enum OceanSpectrum {
+    /// Saturation-range spectrum used by Tessendorf, `alpha` is the Phillips constant
+    Phillips { wind_speed: f32, alpha: f32 },
+    /// Fully developed wind sea (Pierson & Moskowitz, 1964)
+    PiersonMoskowitz { wind_speed: f32 },
+    /// Fetch-limited wind sea (Hasselmann et al., 1973)
+    Jonswap(JonswapParameters),
+    /// JONSWAP with the Kitaigorodskii finite-depth correction (Bouws et al., 1985)
+    Tma { jonswap: JonswapParameters, depth: f32 },
+}
+
+impl Default for OceanSpectrum {
+    fn default() -> Self {
+        Self::Jonswap(JonswapParameters::default())
+    }
+}
+
+impl OceanSpectrum {
+    /// Angular frequency of the spectral peak in rad/s, infinite without wind
+    pub fn peak_frequency(&self) -> f32 {
+        match self {
+            Self::Phillips { wind_speed, .. } | Self::PiersonMoskowitz { wind_speed } if *wind_speed <= 0.0 => {
+                f32::INFINITY
+            }
+            // Maximum of alpha * g^2 / ω^5 * exp(-(g / (U ω))^4)
+            Self::Phillips { wind_speed, .. } => 0.8f32.powf(0.25) * GRAVITY / wind_speed,
+            // 0.877 g / U at 19.5m, converted to the 10m reference height
+            Self::PiersonMoskowitz { wind_speed } => 0.855 * GRAVITY / wind_speed,
+            Self::Jonswap(jonswap) | Self::Tma { jonswap, .. } => jonswap.peak_frequency(),
+        }
+    }
+
+    /// Wind speed 10m above the surface in m/s
+    pub fn wind_speed(&self) -> f32 {
+        match self {
+            Self::Phillips { wind_speed, .. } | Self::PiersonMoskowitz { wind_speed } => *wind_speed,
+            Self::Jonswap(jonswap) | Self::Tma { jonswap, .. } => jonswap.wind_speed,
+        }
+    }
+
+    /// Water depth in meters, `None` for deep water spectra
+    pub fn depth(&self) -> Option<f32> {
+        match self {
+            Self::Tma { depth, .. } => Some(*depth),
+            _ => None,
+        }
+    }
+
+    /// Spectral energy density S(ω) in m²·s at angular frequency `omega`
+    pub fn density(&self, omega: f32) -> f32 {
+        // Without wind there is no wind sea to describe
+        if omega <= 0.0 || self.wind_speed() <= 0.0 {
+            return 0.0;
+        }
+
+        match self {
+            Self::Phillips { wind_speed, alpha } => {
+                let cutoff = GRAVITY / (wind_speed * omega);
+                alpha * GRAVITY * GRAVITY / omega.powi(5) * (-cutoff.powi(4)).exp()
+            }
+            Self::PiersonMoskowitz { .. } => {
+                let peak = self.peak_frequency();
+                0.0081 * GRAVITY * GRAVITY / omega.powi(5) * (-1.25 * (peak / omega).powi(4)).exp()
+            }
+            Self::Jonswap(jonswap) => jonswap.density(omega),
+            Self::Tma { jonswap, depth } => jonswap.density(omega) * kitaigorodskii_depth_factor(omega, *depth),
+        }
+    }
+}
+
+impl JonswapParameters {
+    /// Angular frequency of the spectral peak in rad/s, infinite without wind unless the peak period is given
+    pub fn peak_frequency(&self) -> f32 {
+        match self.peak_period {
+            Some(period) => 2.0 * PI / period,
+            None if self.wind_speed * self.fetch <= 0.0 => f32::INFINITY,
+            None => 22.0 * (GRAVITY * GRAVITY / (self.wind_speed * self.fetch)).cbrt(),
+        }
+    }
+
+    fn density(&self, omega: f32) -> f32 {
+        let peak = self.peak_frequency();
+        let alpha = 0.076 * (self.wind_speed * self.wind_speed / (self.fetch * GRAVITY)).powf(0.22);
+        let sigma = if omega <= peak { 0.07 } else { 0.09 };
+        let r = (-(omega - peak).powi(2) / (2.0 * sigma * sigma * peak * peak)).exp();
+
+        alpha * GRAVITY * GRAVITY / omega.powi(5) * (-1.25 * (peak / omega).powi(4)).exp() * self.gamma.powf(r)
+    }
+}
+
+/// Depth attenuation Φ(ω_h) of the TMA spectrum, approximated piecewise (Thompson & Vincent, 1983)
+fn kitaigorodskii_depth_factor(omega: f32, depth: f32) -> f32 {
+    let omega_h = omega * (depth / GRAVITY).sqrt();
+    if omega_h <= 1.0 {
+        0.5 * omega_h * omega_h
+    } else if omega_h < 2.0 {
+        1.0 - 0.5 * (2.0 - omega_h).powi(2)
+    } else {
+        1.0
+    }
+}
+
+/// Wave number k satisfying the dispersion relation ω² = g k tanh(k h)
+pub fn wave_number_for_frequency(omega: f32, depth: Option<f32>) -> f32 {
+    let deep_water = omega * omega / GRAVITY;
+    let Some(depth) = depth else {
+        return deep_water;
+    };
+
+    // Eckart's approximation as the starting guess, then Newton iterations
+    let mut k = deep_water / (deep_water * depth).tanh().sqrt();
+    for _ in 0..8 {
+        let tanh_kh = (k * depth).tanh();
+        let f = GRAVITY * k * tanh_kh - omega * omega;
+        let df = GRAVITY * (tanh_kh + k * depth * (1.0 - tanh_kh * tanh_kh));
+        k -= f / df;
+    }
+    k
+}
+
+/// How a continuous spectrum is discretized into Gerstner components
+#[derive(Debug, Clone, Copy)]
+pub struct SpectrumSampling {
+    pub component_count: usize,
+    pub min_frequency_ratio: f32,        // Lowest sampled frequency as a multiple of the peak frequency
+    pub max_frequency_ratio: f32,        // Highest sampled frequency as a multiple of the peak frequency
+    pub direction: Vec2,                 // Mean direction of travel on the XZ plane
+    pub spreading: DirectionalSpreading, // How components fan out around `direction`
+    pub steepness: f32,                  // Total Gerstner steepness shared between all components (0.0-1.0)
+    pub seed: u64,                       // Random seed for frequency jitter and phases
+}
+
+impl Default for SpectrumSampling {
+    fn default() -> Self {
+        Self {
+            component_count: 16,
+            min_frequency_ratio: 0.6,
+            max_frequency_ratio: 4.0,
+            direction: Vec2::new(1.0, 0.1).normalize(),
+            spreading: DirectionalSpreading::default(),
+            steepness: 0.5,
+            seed: 0,
+        }
+    }
+}
+
+/// Sample `component_count` Gerstner waves from a spectrum
+///
+/// The frequency range is split into equal bins; each component takes a jittered frequency inside its
+/// bin and the amplitude sqrt(2 S(ω) Δω) so the wave set carries the same variance as the spectrum.
+/// Directions are drawn from the sampling's spreading function around the mean direction. A spectrum
+/// without wind gives no waves.
+pub fn sample_spectrum(spectrum: &OceanSpectrum, sampling: &SpectrumSampling) -> Vec<WaveParameters> {
+    let count = sampling.component_count;
+    if count == 0 || spectrum.wind_speed() <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(sampling.seed);
+    let peak = spectrum.peak_frequency();
+    let min_frequency = peak * sampling.min_frequency_ratio;
+    let max_frequency = peak * sampling.max_frequency_ratio;
+    let bin_width = (max_frequency - min_frequency) / count as f32;
+    let mean_direction = sampling.direction.normalize_or(Vec2::X);
+    let wind_speed = spectrum.wind_speed();
+
+    let mut waves: Vec<WaveParameters> = (0..count)
+        .map(|bin| {
+            let omega = min_frequency + (bin as f32 + rng.r#gen::<f32>()) * bin_width;
+            let wave_number = wave_number_for_frequency(omega, spectrum.depth());
+            let amplitude = (2.0 * spectrum.density(omega) * bin_width).sqrt();
+            let direction = sampling
+                .spreading
+                .sample_direction(mean_direction, omega, peak, wind_speed, &mut rng);
+
+            WaveParameters {
+                amplitude,
+                wavelength: 2.0 * PI / wave_number,
+                speed: omega,
+                direction,
+                steepness: 0.0,
+                wave_number,
+                phase: rng.r#gen::<f32>() * 2.0 * PI,
+            }
+        })
+        .collect();
+
+    // Share the requested steepness between components (Q_i = Q / (k_i A_i N), as in GPU Gems 1, ch. 1)
+    for wave in &mut waves {
+        let k_a = wave.wave_number * wave.amplitude;
+        if k_a > 0.0 {
+            wave.steepness = sampling.steepness / (k_a * count as f32);
+        }
+    }
+    clamp_wave_steepness(&mut waves);
+
+    waves
+}
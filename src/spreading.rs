@@ -0,0 +1,125 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::water::GRAVITY;
+
+/// Number of angular bins used to invert the spreading distribution
+const ANGLE_SAMPLES: usize = 64;
+
+/// Directional spreading function D(θ; ω) distributing wave energy around the mean direction
+#[derive(Debug, Clone, Copy)]
+pub enum DirectionalSpreading {
+    /// Every component travels along the mean direction (long-crested sea)
+    None,
+    /// cos^2s(θ/2) with a frequency-independent exponent (Longuet-Higgins et al., 1963)
+    Cos2s { s: f32 },
+    /// cos-2s with the exponent peaking at `s_max` at the spectral peak (Mitsuyasu et al., 1975).
+    /// Goda suggests 10 for wind seas, 25 for young swell and 75 for long-travelled swell.
+    Mitsuyasu { s_max: f32 },
+    /// cos-2s with the exponent fitted to wind speed and wave age (Hasselmann et al., 1980)
+    Hasselmann,
+    /// sech²(βθ) spreading (Donelan et al., 1985, extended above 1.6 ω_p by Banner, 1990)
+    DonelanBanner,
+}
+
+impl Default for DirectionalSpreading {
+    fn default() -> Self {
+        Self::Mitsuyasu { s_max: 10.0 }
+    }
+}
+
+impl DirectionalSpreading {
+    /// Unnormalized spreading weight at angle `theta` (radians, -π..π) from the mean direction
+    pub fn weight(&self, theta: f32, omega: f32, peak_frequency: f32, wind_speed: f32) -> f32 {
+        let ratio = omega / peak_frequency;
+
+        match *self {
+            Self::None => {
+                if theta == 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Cos2s { s } => cos_2s(theta, s),
+            Self::Mitsuyasu { s_max } => {
+                let s = if ratio <= 1.0 {
+                    s_max * ratio.powi(5)
+                } else {
+                    s_max * ratio.powf(-2.5)
+                };
+                cos_2s(theta, s)
+            }
+            Self::Hasselmann => {
+                let s = if ratio <= 1.0 {
+                    6.97 * ratio.powf(4.06)
+                } else {
+                    // Wave age U / c_p with the deep water phase speed of the peak
+                    let wave_age = wind_speed * peak_frequency / GRAVITY;
+                    9.77 * ratio.powf(-2.33 - 1.45 * (wave_age - 1.17))
+                };
+                cos_2s(theta, s)
+            }
+            Self::DonelanBanner => {
+                let beta = if ratio < 0.95 {
+                    2.61 * ratio.max(0.56).powf(1.3)
+                } else if ratio < 1.6 {
+                    2.28 * ratio.powf(-1.3)
+                } else {
+                    let epsilon = -0.4 + 0.8393 * (-0.567 * (ratio * ratio).ln()).exp();
+                    10f32.powf(epsilon)
+                };
+                let sech = 1.0 / (beta * theta).cosh();
+                sech * sech
+            }
+        }
+    }
+
+    /// Draw a direction of travel for a component of angular frequency `omega`
+    ///
+    /// The angle is sampled by inverting the cumulative spreading distribution, tabulated over
+    /// `ANGLE_SAMPLES` bins, and rotated away from `mean_direction`.
+    pub fn sample_direction(
+        &self,
+        mean_direction: Vec2,
+        omega: f32,
+        peak_frequency: f32,
+        wind_speed: f32,
+        rng: &mut impl Rng,
+    ) -> Vec2 {
+        let u: f32 = rng.r#gen();
+        if let Self::None = self {
+            return mean_direction;
+        }
+
+        let bin_width = 2.0 * PI / ANGLE_SAMPLES as f32;
+        let mut cumulative = [0.0f32; ANGLE_SAMPLES + 1];
+        let mut previous = self.weight(-PI, omega, peak_frequency, wind_speed);
+        for bin in 0..ANGLE_SAMPLES {
+            let theta = -PI + (bin + 1) as f32 * bin_width;
+            let current = self.weight(theta, omega, peak_frequency, wind_speed);
+            cumulative[bin + 1] = cumulative[bin] + 0.5 * (previous + current) * bin_width;
+            previous = current;
+        }
+
+        let total = cumulative[ANGLE_SAMPLES];
+        if total <= 0.0 {
+            return mean_direction;
+        }
+
+        let target = u * total;
+        let bin = cumulative.partition_point(|&c| c < target).clamp(1, ANGLE_SAMPLES) - 1;
+        let bin_mass = cumulative[bin + 1] - cumulative[bin];
+        let t = if bin_mass > 0.0 { (target - cumulative[bin]) / bin_mass } else { 0.5 };
+        let theta = -PI + (bin as f32 + t) * bin_width;
+
+        Vec2::from_angle(theta).rotate(mean_direction)
+    }
+}
+
+/// cos^2s(θ/2), which is non-negative over -π..π
+fn cos_2s(theta: f32, s: f32) -> f32 {
+    (0.5 * theta).cos().max(0.0).powf(2.0 * s)
+}
@@ -0,0 +1,339 @@
+use std::sync::LazyLock;
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+use crate::{
+    board_presets::BoardPreset,
+    hull_buoyancy::{BuoyancyHull, SubmergedTriangle},
+    hydrodynamics::Hydrodynamics,
+    rigid_body::{MassProperties, Velocity},
+    surfboard_shape::{BoardShape, TailShape},
+    water::{GRAVITY, WaterSpawnSettings},
+    water_query::WaterQuery,
+};
+
+/// Floats an entity on the water. Mass comes from a `Surfboard` on the same entity if there is one, and
+/// otherwise from the buoyancy points, plus any rider. See `BuoyancyPointsBuilder` for points to fit other
+/// shapes and `BoardPreset` for bodies matching common boards.
+#[derive(Component, Debug)]
+pub struct FloatingBody {
+    pub buoyancy_points: Vec<Vec3>, // Relative positions from entity center to sample water height
+    pub point_volumes: Vec<f32>,    // m³ each buoyancy point stands for, shared out evenly if not one per point
+    pub point_height: f32,          // Vertical extent of each point's volume, over which it goes under
+    pub submerged_volume: f32,      // m³ below the water surface as of the last physics tick
+    pub water_density: f32,
+    pub body_density: f32,
+    pub rider_mass: f32,       // kg of rider moving with the body as a point mass at `rider_offset`
+    pub rider_offset: Vec3,    // Rider's centre of mass relative to the body origin, in body axes
+    pub drag_coefficient: f32, // Rate (1/s) at which motion is damped while fully submerged, without a hull
+    pub hydrodynamics: Hydrodynamics, // Forces on the submerged faces of a `BuoyancyHull`
+    pub velocity: Velocity,
+    pub swept: Vec<f32>, // Per hull triangle, submerged area times speed through the water as of the last tick
+}
+
+/// Volume of `Surfboard::default()`, shaped once rather than on every `FloatingBody::default()`
+static DEFAULT_BOARD_VOLUME: LazyLock<f32> = LazyLock::new(|| Surfboard::default().volume());
+
+impl Default for FloatingBody {
+    fn default() -> Self {
+        Self {
+            // Sample points for a surfboard - corners and center
+            buoyancy_points: vec![
+                Vec3::new(-1.5, 0.0, -0.3),  // Front left
+                Vec3::new(1.5, 0.0, -0.3),   // Front right
+                Vec3::new(-1.5, 0.0, 0.3),   // Back left
+                Vec3::new(1.5, 0.0, 0.3),    // Back right
+                Vec3::new(0.0, 0.0, 0.0),    // Center
+            ],
+            point_volumes: vec![*DEFAULT_BOARD_VOLUME / 5.0; 5], // An equal share of the board each
+            point_height: Surfboard::default().thickness,
+            submerged_volume: 0.0,
+            water_density: 1000.0,   // kg/m³
+            body_density: 200.0,     // Surfboard is much lighter than water
+            rider_mass: 0.0,
+            rider_offset: Vec3::Y, // Standing on the deck, hips about a metre up
+            drag_coefficient: 1.0,
+            hydrodynamics: Hydrodynamics::default(),
+            velocity: Velocity::default(),
+            swept: Vec::new(),
+        }
+    }
+}
+
+impl FloatingBody {
+    /// Shares the total of `point_volumes` evenly between the buoyancy points unless there is one volume per
+    /// point, as when only `buoyancy_points` is overridden with `..default()`
+    fn match_point_volumes(&mut self) {
+        let count = self.buoyancy_points.len();
+        if self.point_volumes.len() != count {
+            let total: f32 = self.point_volumes.iter().sum();
+            self.point_volumes = vec![total / count.max(1) as f32; count];
+        }
+    }
+}
+
+/// Shaper's measurements for a board, nose towards +X. Widths and thicknesses are full, not half, sizes.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Surfboard {
+    pub length: f32,
+    pub width: f32,          // At the wide point
+    pub thickness: f32,      // At the middle
+    pub nose_width: f32,     // A foot back from the nose
+    pub tail_width: f32,     // A foot up from the tail
+    pub wide_point: f32,     // Metres ahead of the middle, negative behind it
+    pub nose_rocker: f32,    // Lift of the bottom at the nose above the middle
+    pub tail_rocker: f32,
+    pub nose_thickness: f32, // Foil: fraction of the thickness left at the nose
+    pub tail_thickness: f32,
+    pub rail_apex: f32,      // Height of the rail's widest point, as a fraction of the thickness (0.5 for 50/50)
+    pub rail_fullness: f32,  // Superellipse exponent of the rails: 2 round and soft, higher boxier and harder
+    pub tail_shape: TailShape,
+}
+
+impl Default for Surfboard {
+    fn default() -> Self {
+        Self {
+            length: 3.0,   // 3 meter surfboard
+            width: 0.6,    // 60cm wide
+            thickness: 0.1, // 10cm thick
+            nose_width: 0.45,
+            tail_width: 0.38,
+            wide_point: 0.0,
+            nose_rocker: 0.12,
+            tail_rocker: 0.05,
+            nose_thickness: 0.4,
+            tail_thickness: 0.5,
+            rail_apex: 0.4,
+            rail_fullness: 3.0,
+            tail_shape: TailShape::Squash,
+        }
+    }
+}
+
+impl Surfboard {
+    /// Volume of the shaped board in m³
+    pub fn volume(&self) -> f32 {
+        BoardShape::new(self).volume()
+    }
+
+    /// Volume in litres, as boards are sold
+    pub fn volume_litres(&self) -> f32 {
+        self.volume() * 1000.0
+    }
+
+    /// Mass and inertia of the shaped board as a solid of `density`, about its centre of volume
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        let shape = BoardShape::new(self);
+        MassProperties::from_triangles(density, &shape.positions, &shape.triangles)
+    }
+}
+
+/// Smooth, UV-mapped mesh of the shaped board, centred on its centre of volume
+pub fn create_surfboard_mesh(surfboard: &Surfboard) -> Mesh {
+    let shape = BoardShape::new(surfboard);
+    
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    
+    let positions: Vec<_> = shape.positions.iter().map(|&p| p.to_array()).collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, shape.normals.iter().map(|&n| n.to_array()).collect::<Vec<_>>());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, shape.uvs.iter().map(|&uv| uv.to_array()).collect::<Vec<_>>());
+    mesh.insert_indices(Indices::U32(shape.triangles.into_iter().flatten().collect()));
+    
+    mesh
+}
+
+pub fn spawn_surfboard(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    water: Res<WaterSpawnSettings>,
+) {
+    let (surfboard, floating_body, hull) = BoardPreset::Longboard.bundle();
+    let mesh_handle = meshes.add(create_surfboard_mesh(&surfboard));
+    
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgb(1.0, 1.0, 0.8), // Off-white surfboard color
+        perceptual_roughness: 0.8,
+        metallic: 0.0,
+        ..default()
+    });
+    
+    commands.spawn((
+        Mesh3d(mesh_handle),
+        MeshMaterial3d(material),
+        Transform::from_translation(water.transform.translation + Vec3::new(0.0, 2.0, 0.0)), // Start above water
+        surfboard,
+        floating_body,
+        hull,
+    ));
+}
+
+/// Scratch buffers for batching every buoyancy point and hull vertex of every body into one water query
+#[derive(Default)]
+pub struct BuoyancySamples {
+    points: Vec<Vec3>,
+    heights: Vec<f32>,
+    clipped: Vec<SubmergedTriangle>,
+    centroids: Vec<Vec3>,
+    water_velocities: Vec<Vec3>, // At the centroids
+    board_masses: EntityHashMap<MassProperties>, // Shaping a board is too slow to redo every tick
+}
+
+/// Bodies with a `BuoyancyHull` float on the clipped volume of their hull and feel hydrodynamic forces on
+/// its submerged faces. The rest float on their buoyancy points with simple drag.
+pub fn update_surfboard_physics(
+    time: Res<Time>,
+    mut water: WaterQuery,
+    mut surfboard_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut FloatingBody,
+        Option<Ref<Surfboard>>,
+        Option<&BuoyancyHull>,
+    )>,
+    mut samples: Local<BuoyancySamples>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+    
+    // Gather all buoyancy points in world space and query the water heights in one batch
+    let samples = &mut *samples;
+    samples.points.clear();
+    for (_, transform, floating_body, _, hull) in surfboard_query.iter() {
+        let points = hull.map_or(&floating_body.buoyancy_points, |hull| &hull.vertices);
+        for point in points {
+            samples.points.push(transform.translation + transform.rotation * *point);
+        }
+    }
+    samples.heights.resize(samples.points.len(), 0.0);
+    
+    // Without any water the bodies simply fall
+    if !water.heights(&samples.points, &mut samples.heights) {
+        samples.heights.fill(f32::NEG_INFINITY);
+    }
+    let mut sample_start = 0;
+    
+    samples.board_masses.retain(|entity, _| surfboard_query.contains(*entity));
+    for (entity, mut transform, mut floating_body, surfboard, hull) in surfboard_query.iter_mut() {
+        floating_body.match_point_volumes();
+        let mass = match surfboard {
+            Some(surfboard) => {
+                // Cached at unit density, so the body density can change freely
+                if surfboard.is_changed() {
+                    samples.board_masses.remove(&entity);
+                }
+                let unit = *samples.board_masses.entry(entity).or_insert_with(|| surfboard.mass_properties(1.0));
+                MassProperties {
+                    mass: unit.mass * floating_body.body_density,
+                    centre: unit.centre,
+                    inertia: unit.inertia * floating_body.body_density,
+                }
+            }
+            None => MassProperties::from_points(
+                floating_body.body_density,
+                &floating_body.buoyancy_points,
+                &floating_body.point_volumes,
+            ),
+        };
+        if mass.mass <= 0.0 {
+            sample_start += hull.map_or(floating_body.buoyancy_points.len(), |hull| hull.vertices.len());
+            continue;
+        }
+        // The rider raises the centre of gravity and adds their own inertia about it
+        let mass = mass.with_point_mass(floating_body.rider_mass, floating_body.rider_offset);
+        let centre_of_mass = transform.translation + transform.rotation * mass.centre;
+        let mut force = Vec3::NEG_Y * mass.mass * GRAVITY;
+        let mut torque = Vec3::ZERO;
+        
+        if let Some(hull) = hull {
+            let samples_range = sample_start..sample_start + hull.vertices.len();
+            sample_start = samples_range.end;
+            let submerged = hull.submerged(
+                &samples.points[samples_range.clone()],
+                &samples.heights[samples_range],
+                &mut samples.clipped,
+            );
+            
+            // Archimedes on the clipped shape, acting through its centre of buoyancy
+            let buoyancy = Vec3::Y * floating_body.water_density * GRAVITY * submerged.volume;
+            force += buoyancy;
+            torque += (submerged.centre - centre_of_mass).cross(buoyancy);
+            floating_body.submerged_volume = submerged.volume;
+            
+            // The water's orbital velocity under every submerged piece in one batch
+            samples.centroids.clear();
+            samples.centroids.extend(samples.clipped.iter().map(SubmergedTriangle::centroid));
+            samples.water_velocities.resize(samples.centroids.len(), Vec3::ZERO);
+            if !water.velocities_at_depth(&samples.centroids, &mut samples.water_velocities) {
+                samples.water_velocities.fill(Vec3::ZERO);
+            }
+            
+            let length = hull.length();
+            let body = &mut *floating_body;
+            let (drag, drag_torque) = body.hydrodynamics.hull_forces(
+                hull,
+                &mut body.swept,
+                &samples.clipped,
+                &samples.water_velocities,
+                centre_of_mass,
+                &body.velocity,
+                mass.mass,
+                length,
+                body.water_density,
+                dt,
+            );
+            
+            // Drag is applied explicitly, so drag strong enough to reverse the motion within a tick would feed
+            // it instead of damping it. A rider far above a light board makes the spin especially stiff.
+            let overshoot = |change: Vec3, velocity: Vec3| {
+                (-change.dot(velocity) / velocity.length_squared().max(f32::EPSILON)).max(1.0)
+            };
+            let spin_change = mass.world_inertia(transform.rotation).inverse() * drag_torque * dt;
+            force += drag / overshoot(drag * dt / mass.mass, body.velocity.linear);
+            torque += drag_torque / overshoot(spin_change, body.velocity.angular);
+        } else {
+            let height = floating_body.point_height.max(f32::EPSILON);
+            let mut submerged_volume = 0.0;
+            
+            let points = floating_body.buoyancy_points.iter().zip(&floating_body.point_volumes);
+            for ((buoyancy_point, &volume), &water_height) in points.zip(&samples.heights[sample_start..]) {
+                let offset = transform.rotation * *buoyancy_point;
+                
+                // How much of the point's volume is below the surface, filling from the bottom up
+                let bottom = transform.translation.y + offset.y - height / 2.0;
+                let submersion = ((water_height - bottom) / height).clamp(0.0, 1.0) * volume;
+                if submersion > 0.0 {
+                    // Archimedes: the weight of the displaced water
+                    let buoyancy = Vec3::Y * floating_body.water_density * GRAVITY * submersion;
+                    force += buoyancy;
+                    torque += (transform.translation + offset - centre_of_mass).cross(buoyancy);
+                    submerged_volume += submersion;
+                }
+            }
+            sample_start += floating_body.buoyancy_points.len();
+            floating_body.submerged_volume = submerged_volume;
+            
+            // Damp linear and angular motion in proportion to how much of the body is in the water
+            let volume: f32 = floating_body.point_volumes.iter().sum();
+            let damping = floating_body.drag_coefficient * (submerged_volume / volume).min(1.0);
+            let velocity = floating_body.velocity;
+            force -= velocity.linear * mass.mass * damping;
+            torque -= mass.world_inertia(transform.rotation) * velocity.angular * damping;
+        }
+        
+        floating_body.velocity.integrate(&mut transform, &mass, force, torque, dt);
+    }
+}
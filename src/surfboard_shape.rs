@@ -0,0 +1,269 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::surfboard::Surfboard;
+
+const STATIONS: usize = 48; // Cross-sections from the tail, or a swallow's notch, to the nose
+const NOTCH_STATIONS: usize = 8; // Cross-sections through each lobe behind a swallow's notch
+const RING: usize = 24; // Vertices around each cross-section
+const MEASURE_POINT: f32 = 0.3; // Nose and tail widths are taken a foot in from the ends, in metres
+const TAIL_BLOCK: f32 = 0.75; // Width across the end of a square, squash or swallow tail, of the tail width
+const SQUASH_CORNER: f32 = 0.4; // Corner radius of a squash tail, of half the block
+const SWALLOW_NOTCH: f32 = 0.8; // Depth of a swallow tail's notch, of half the block
+const SWALLOW_TIP: f32 = 0.2; // Width left across the end of each of a swallow's points, of half the block
+
+/// Outline of the tail in plan view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TailShape {
+    Square,
+    #[default]
+    Squash, // Square with rounded corners
+    Pin,     // Narrowing to a point
+    Swallow, // Square with a V cut into it, leaving two points
+}
+
+/// Closed, outward-wound triangle surface of a shaped board, centred on its centre of volume
+pub(crate) struct BoardShape {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<Vec2>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl BoardShape {
+    pub fn new(surfboard: &Surfboard) -> Self {
+        let half_length = surfboard.length / 2.0;
+        let tail_end = tail_end_half_width(surfboard);
+        // A swallow's body stops at the apex of its notch, behind which each side is shaped as a lobe of its own
+        let notch = match surfboard.tail_shape {
+            TailShape::Swallow => tail_end * SWALLOW_NOTCH,
+            _ => 0.0,
+        };
+        let start = -half_length + notch;
+
+        // Stations bunch up towards the ends, where the outline and rocker curve fastest
+        let mut positions = Vec::with_capacity(STATIONS * RING + 2 * NOTCH_STATIONS * (RING / 2 + 3) + RING + 1);
+        for station in 0..STATIONS {
+            let along = (1.0 - (PI * station as f32 / (STATIONS - 1) as f32).cos()) / 2.0;
+            let x = start + along * (half_length - start);
+            let half_width = half_width(surfboard, x);
+            for vertex in 0..RING {
+                let section = rail_section(surfboard, TAU * vertex as f32 / RING as f32);
+                positions.push(section_point(surfboard, x, section, section.x * half_width));
+            }
+        }
+
+        let mut triangles = Vec::with_capacity(2 * (STATIONS - 1) * RING + 2 * NOTCH_STATIONS * (RING + 2) + RING);
+        for station in 0..STATIONS - 1 {
+            let (this, next) = ((station * RING) as u32, ((station + 1) * RING) as u32);
+            for vertex in 0..RING as u32 {
+                let following = (vertex + 1) % RING as u32;
+                triangles.push([this + vertex, next + vertex, this + following]);
+                triangles.push([next + vertex, next + following, this + following]);
+            }
+        }
+
+        if notch > 0.0 {
+            for side in [1.0, -1.0] {
+                // Half of the first ring, from the stringer at the bottom round this side's rail to the stringer on
+                // the deck, which a wall across the notch closes back to the bottom
+                let first = if side > 0.0 { 3 * RING / 4 } else { RING / 4 };
+                let half_ring: Vec<usize> = (0..=RING / 2).map(|step| (first + step) % RING).collect();
+                let mut rings = vec![half_ring.iter().map(|&vertex| vertex as u32).collect::<Vec<_>>()];
+
+                // The notch's edge runs straight from the stringer at the apex to just inside the rail at the tail
+                for station in 1..=NOTCH_STATIONS {
+                    let behind = station as f32 / NOTCH_STATIONS as f32;
+                    let x = start - behind * notch;
+                    let (inner, outer) = (behind * (1.0 - SWALLOW_TIP) * tail_end, half_width(surfboard, x));
+                    rings.push((positions.len()..positions.len() + half_ring.len()).map(|i| i as u32).collect());
+                    for &vertex in &half_ring {
+                        let section = rail_section(surfboard, TAU * vertex as f32 / RING as f32);
+                        let z = side * (inner + section.x.abs() * (outer - inner));
+                        positions.push(section_point(surfboard, x, section, z));
+                    }
+                }
+
+                // Flat-shaded walls get their own copies of the deck and bottom edges
+                let walls: Vec<_> = rings
+                    .iter()
+                    .map(|ring| {
+                        let (top, bottom) = (ring[ring.len() - 1], ring[0]);
+                        positions.extend([top, bottom].map(|vertex| positions[vertex as usize]));
+                        [positions.len() as u32 - 2, positions.len() as u32 - 1]
+                    })
+                    .collect();
+
+                // Rings run tailwards here, so each pair is wound with the one behind as `this`
+                for (pair, wall) in rings.windows(2).zip(walls.windows(2)) {
+                    let (next, this) = (&pair[0], &pair[1]);
+                    for vertex in 0..RING / 2 {
+                        triangles.push([this[vertex], next[vertex], this[vertex + 1]]);
+                        triangles.push([next[vertex], next[vertex + 1], this[vertex + 1]]);
+                    }
+                    let (next, this) = (wall[0], wall[1]);
+                    triangles.push([this[0], next[0], this[1]]);
+                    triangles.push([next[0], next[1], this[1]]);
+                }
+                let tip = &rings[NOTCH_STATIONS];
+                cap(&mut positions, &mut triangles, tip.iter().map(|&vertex| vertex as usize));
+            }
+        } else if tail_end > 0.0 {
+            cap(&mut positions, &mut triangles, 0..RING);
+        }
+
+        // Smooth normals around the rails and along the board. The nose narrows to nothing, and the cap and
+        // notch walls share no vertices with the rest, so they follow only their own faces.
+        let mut normals = vec![Vec3::ZERO; positions.len()];
+        for &[a, b, c] in &triangles {
+            let [pa, pb, pc] = [a, b, c].map(|vertex| positions[vertex as usize]);
+            let area_normal = (pb - pa).cross(pc - pa);
+            for vertex in [a, b, c] {
+                normals[vertex as usize] += area_normal;
+            }
+        }
+
+        // Balance the board on its centre of volume so it can be the body origin
+        let centre = centre_of_volume(&positions, &triangles);
+        for position in &mut positions {
+            *position -= centre;
+        }
+        for normal in &mut normals {
+            *normal = normal.normalize_or(Vec3::Y);
+        }
+
+        // Planar from above, so deck and bottom art both run nose to tail
+        let uvs = positions
+            .iter()
+            .map(|position| {
+                Vec2::new(
+                    (position.x + centre.x) / surfboard.length + 0.5,
+                    position.z / surfboard.width.max(f32::EPSILON) + 0.5,
+                )
+            })
+            .collect();
+
+        Self {
+            positions,
+            normals,
+            uvs,
+            triangles,
+        }
+    }
+
+    /// Enclosed volume in m³
+    pub fn volume(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let [a, b, c] = [a, b, c].map(|vertex| self.positions[vertex as usize]);
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+}
+
+fn centre_of_volume(positions: &[Vec3], triangles: &[[u32; 3]]) -> Vec3 {
+    let (mut volume, mut moment) = (0.0, Vec3::ZERO);
+    for &[a, b, c] in triangles {
+        let [a, b, c] = [a, b, c].map(|vertex| positions[vertex as usize]);
+        let tetrahedron = a.dot(b.cross(c)) / 6.0;
+        volume += tetrahedron;
+        moment += tetrahedron * (a + b + c) / 4.0;
+    }
+    if volume > 0.0 { moment / volume } else { Vec3::ZERO }
+}
+
+/// Half the width across the tail end
+fn tail_end_half_width(surfboard: &Surfboard) -> f32 {
+    match surfboard.tail_shape {
+        TailShape::Pin => 0.0,
+        _ => surfboard.tail_width / 2.0 * TAIL_BLOCK,
+    }
+}
+
+/// Exponent for `1 - u^k` to pass through `ratio` a foot in from the end of a `span`
+fn outline_exponent(span: f32, ratio: f32) -> f32 {
+    let measured = ((span - MEASURE_POINT) / span).clamp(0.01, 0.99);
+    (1.0 - ratio.clamp(0.01, 0.99)).ln() / measured.ln()
+}
+
+/// Half the board's width at `x`, nose towards +X, widest at the wide point
+fn half_width(surfboard: &Surfboard, x: f32) -> f32 {
+    let half_length = surfboard.length / 2.0;
+    let half_max = surfboard.width / 2.0;
+    let wide_point = surfboard.wide_point.clamp(-half_length * 0.9, half_length * 0.9);
+
+    if x >= wide_point {
+        // A round nose, with a vertical tangent at the tip
+        let span = half_length - wide_point;
+        let ratio = (surfboard.nose_width / surfboard.width).powi(2);
+        let u = ((x - wide_point) / span).clamp(0.0, 1.0);
+        half_max * (1.0 - u.powf(outline_exponent(span, ratio))).max(0.0).sqrt()
+    } else {
+        // Straighter lines into the tail block, or to the point of a pin
+        let span = half_length + wide_point;
+        let end = tail_end_half_width(surfboard);
+        let ratio = (surfboard.tail_width / 2.0 - end) / (half_max - end).max(f32::EPSILON);
+        let u = ((wide_point - x) / span).clamp(0.0, 1.0);
+        let half_width = end + (half_max - end) * (1.0 - u.powf(outline_exponent(span, ratio))).max(0.0);
+        // Round off the corners of a squash's block
+        let radius = end * SQUASH_CORNER;
+        let from_tail = (x + half_length).max(0.0);
+        if surfboard.tail_shape == TailShape::Squash && from_tail < radius {
+            half_width.min(end - radius + (radius * radius - (radius - from_tail).powi(2)).sqrt())
+        } else {
+            half_width
+        }
+    }
+}
+
+/// Height of the bottom above its lowest point at the middle of the board
+fn rocker(surfboard: &Surfboard, x: f32) -> f32 {
+    let u = x / (surfboard.length / 2.0);
+    if u >= 0.0 {
+        surfboard.nose_rocker * u.powf(2.5)
+    } else {
+        surfboard.tail_rocker * u.powi(2)
+    }
+}
+
+/// Thickness at `x`, thinning from the middle towards the nose and tail
+fn foil(surfboard: &Surfboard, x: f32) -> f32 {
+    let u = x / (surfboard.length / 2.0);
+    let end = if u >= 0.0 { surfboard.nose_thickness } else { surfboard.tail_thickness };
+    surfboard.thickness * (end + (1.0 - end) * (1.0 - u * u).max(0.0))
+}
+
+/// Point around the rail at `angle` (from the rail edge, up over the deck), as a fraction of the half
+/// width across and of the thickness up from the bottom. A superellipse either side of the rail apex.
+fn rail_section(surfboard: &Surfboard, angle: f32) -> Vec2 {
+    let exponent = 2.0 / surfboard.rail_fullness.max(1.0);
+    let curve = |value: f32| value.signum() * value.abs().powf(exponent);
+    let (sin, cos) = angle.sin_cos();
+    let apex = surfboard.rail_apex.clamp(0.05, 0.95);
+    let height = if sin >= 0.0 { 1.0 - apex } else { apex };
+    Vec2::new(curve(cos), apex + height * curve(sin))
+}
+
+/// Closes the tail end of the `ring` of vertices, given in the order the rings around it are wound, with a
+/// fan around its centre. The cap gets its own copies of the vertices, so its normals follow only its faces.
+fn cap(positions: &mut Vec<Vec3>, triangles: &mut Vec<[u32; 3]>, ring: impl Iterator<Item = usize>) {
+    let ring_start = positions.len();
+    for vertex in ring {
+        positions.push(positions[vertex]);
+    }
+    let count = positions.len() - ring_start;
+    let centre = positions[ring_start..].iter().sum::<Vec3>() / count as f32;
+    positions.push(centre);
+    for vertex in 0..count {
+        let following = (vertex + 1) % count;
+        triangles.push([ring_start + count, ring_start + vertex, ring_start + following].map(|index| index as u32));
+    }
+}
+
+/// Point on the cross-section at `x` given by a `rail_section` point, placed `z` off the stringer
+fn section_point(surfboard: &Surfboard, x: f32, section: Vec2, z: f32) -> Vec3 {
+    Vec3::new(x, rocker(surfboard, x) + section.y * foil(surfboard, x), z)
+}
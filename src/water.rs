@@ -1,18 +1,151 @@
 use bevy::{
     prelude::*,
     render::{
-        mesh::{Indices, PrimitiveTopology},
+        mesh::{Indices, MeshAabb, PrimitiveTopology, VertexAttributeValues},
         render_asset::RenderAssetUsages,
+        view::NoFrustumCulling,
     },
+    tasks::{ComputeTaskPool, TaskPool},
 };
-use wide::f32x4;
+use wide::f32x8;
+
+use crate::{
+    batch_query::wave_phase,
+    clipmap::{WaterClipmap, create_clipmap_mesh, follow_camera_with_clipmap, stitch_seams},
+    fft_ocean::{FftOcean, simulate_fft_ocean},
+    simd::{LaneWidth, MAX_LANES, SimdF32, SinCosPrecision, lane_dispatch, sin_cos},
+    spectrum::{OceanSpectrum, SpectrumSampling, sample_spectrum},
+    surfboard::{spawn_surfboard, update_surfboard_physics},
+    water_material::{FlatWaterBounds, GerstnerExtension, WaterMaterial, WaterMaterialPlugin},
+    water_tiles::{WaterTile, WaterTiles, spawn_water_tiles, update_water_tiles},
+};
+
+pub const GRAVITY: f32 = 9.81;
 
 #[derive(Component, Debug)]
 pub struct WaterSurface {
     pub grid_size: usize,
     pub world_size: f32,
     pub vertex_count: usize,
-    pub base_positions: Vec<Vec3>,
+    pub base_positions: BasePositions,
+    pub seams: Vec<[u32; 3]>, // Vertices kept on the edge between clipmap levels, see `stitch_seams`
+}
+
+/// Base vertex positions of a water mesh as structure-of-arrays lanes. X and Z are padded with the
+/// last vertex to a multiple of `MAX_LANES`, so the displacement kernels load whole, aligned vectors
+/// straight out of them at every lane width, with no gather and no partial last chunk.
+#[derive(Debug, Clone, Default)]
+pub struct BasePositions {
+    len: usize,
+    generation: u32, // Bumped by every `set`, so caches built from older positions can tell
+    x: Vec<LaneBlock>,
+    z: Vec<LaneBlock>,
+    wave_keys: Vec<[f32; 3]>, // Direction and wave number of every wave `wave_dots` holds
+    wave_dots: Vec<LaneBlock>, // k (d · p) of every wave, one padded run of lanes per wave
+}
+
+/// `MAX_LANES` floats on a 64 byte boundary, which covers the alignment of every lane width
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(C, align(64))]
+struct LaneBlock([f32; MAX_LANES]);
+
+fn lanes(blocks: &[LaneBlock]) -> &[f32] {
+    // SAFETY: `LaneBlock` is a 64 byte `[f32; MAX_LANES]`, so its alignment adds no padding
+    unsafe { std::slice::from_raw_parts(blocks.as_ptr().cast(), blocks.len() * MAX_LANES) }
+}
+
+fn lanes_mut(blocks: &mut [LaneBlock]) -> &mut [f32] {
+    // SAFETY: as in `lanes`
+    unsafe { std::slice::from_raw_parts_mut(blocks.as_mut_ptr().cast(), blocks.len() * MAX_LANES) }
+}
+
+impl BasePositions {
+    pub fn new(positions: &[Vec3]) -> Self {
+        let mut base_positions = Self::default();
+        base_positions.set(positions);
+        base_positions
+    }
+
+    /// Replace every position, reusing the existing allocations
+    pub fn set(&mut self, positions: &[Vec3]) {
+        self.len = positions.len();
+        let blocks = self.padded_len() / MAX_LANES;
+        let last = positions.last().copied().unwrap_or_default();
+
+        self.x.resize(blocks, LaneBlock::default());
+        self.z.resize(blocks, LaneBlock::default());
+        let x = lanes_mut(&mut self.x);
+        let z = lanes_mut(&mut self.z);
+        for (lane, (x, z)) in x.iter_mut().zip(z.iter_mut()).enumerate() {
+            let pos = positions.get(lane).unwrap_or(&last);
+            (*x, *z) = (pos.x, pos.z);
+        }
+
+        // Recomputed by the next `cache_wave_dots`
+        self.wave_keys.clear();
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Length of the `x` and `z` lanes, `len` rounded up to a multiple of `MAX_LANES`
+    pub fn padded_len(&self) -> usize {
+        self.len.next_multiple_of(MAX_LANES)
+    }
+
+    pub fn x(&self) -> &[f32] {
+        lanes(&self.x)
+    }
+
+    pub fn z(&self) -> &[f32] {
+        lanes(&self.z)
+    }
+
+    pub fn get(&self, index: usize) -> Vec3 {
+        assert!(index < self.len, "base position {index} out of {}", self.len);
+        Vec3::new(self.x()[index], 0.0, self.z()[index])
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Vec3> + '_ {
+        self.x()[..self.len].iter().zip(&self.z()[..self.len]).map(|(&x, &z)| Vec3::new(x, 0.0, z))
+    }
+
+    /// Precompute k (d · p) of every wave, which the kernels then load instead of recomputing it
+    /// each frame. Only redone when a direction or wave number changed; costs one f32 per wave and vertex.
+    pub fn cache_wave_dots(&mut self, waves: &[WaveParameters]) {
+        if self.wave_dots(waves).is_some() {
+            return;
+        }
+
+        self.wave_keys.clear();
+        self.wave_keys.extend(waves.iter().map(wave_key));
+        self.wave_dots.resize(waves.len() * self.x.len(), LaneBlock::default());
+
+        let padded_len = self.padded_len();
+        let (x, z) = (lanes(&self.x), lanes(&self.z));
+        for (wave, dots) in waves.iter().zip(lanes_mut(&mut self.wave_dots).chunks_mut(padded_len.max(1))) {
+            for (dot, (x, z)) in dots.iter_mut().zip(x.iter().zip(z)) {
+                *dot = wave.wave_number * (x * wave.direction.x + z * wave.direction.y);
+            }
+        }
+    }
+
+    /// The k (d · p) lanes from `cache_wave_dots`, if they were computed for these waves
+    pub fn wave_dots(&self, waves: &[WaveParameters]) -> Option<&[f32]> {
+        let cached =
+            self.wave_keys.len() == waves.len() && self.wave_keys.iter().copied().eq(waves.iter().map(wave_key));
+        (cached && !waves.is_empty()).then(|| lanes(&self.wave_dots))
+    }
+}
+
+fn wave_key(wave: &WaveParameters) -> [f32; 3] {
+    [wave.direction.x, wave.direction.y, wave.wave_number]
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -23,6 +156,50 @@ pub struct WaveParameters {
     pub direction: Vec2,
     pub steepness: f32, // Q parameter for Gerstner waves (0.0-1.0)
     pub wave_number: f32, // k = 2π/L (pre-calculated for performance)
+    pub phase: f32, // Phase offset in radians
+}
+
+/// Limits a water entity to a local-space XZ rectangle centred on its transform.
+/// Water entities without bounds extend forever, like an open ocean.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WaterBounds {
+    pub half_extents: Vec2,  // Half size along local X and Z in meters
+    pub blend_distance: f32, // Band inside the edge where this body fades into lower priority bodies
+    pub priority: i32,       // Where bodies overlap, higher priority ones cover lower ones
+}
+
+impl WaterBounds {
+    pub fn new(half_extents: Vec2) -> Self {
+        Self {
+            half_extents,
+            blend_distance: 0.0,
+            priority: 1,
+        }
+    }
+
+    pub fn with_blend_distance(mut self, blend_distance: f32) -> Self {
+        self.blend_distance = blend_distance;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// How much of this body covers the local XZ `position`: 1.0 inside, fading to 0.0 at the edge
+    pub fn coverage(&self, position: Vec2) -> f32 {
+        let inside = self.half_extents - position.abs();
+        let distance = inside.min_element();
+        if distance < 0.0 {
+            0.0
+        } else if self.blend_distance <= 0.0 {
+            1.0
+        } else {
+            let t = (distance / self.blend_distance).min(1.0);
+            t * t * (3.0 - 2.0 * t)
+        }
+    }
 }
 
 #[derive(Component, Debug)]
@@ -41,6 +218,7 @@ impl Default for WaterWaves {
                 direction: Vec2::new(1.0, 0.1).normalize(),
                 steepness: 0.15,
                 wave_number: 2.0 * std::f32::consts::PI / 25.0,
+                phase: 0.0,
             },
             // Wave 2: Medium wave with slight angle variation
             WaveParameters {
@@ -50,6 +228,7 @@ impl Default for WaterWaves {
                 direction: Vec2::new(0.9, 0.2).normalize(),
                 steepness: 0.18,
                 wave_number: 2.0 * std::f32::consts::PI / 18.0,
+                phase: 0.0,
             },
             // Wave 3: Smaller wave for detail
             WaveParameters {
@@ -59,6 +238,7 @@ impl Default for WaterWaves {
                 direction: Vec2::new(1.1, -0.1).normalize(),
                 steepness: 0.2,
                 wave_number: 2.0 * std::f32::consts::PI / 12.0,
+                phase: 0.0,
             },
             // Wave 4: Smallest wave for surface texture
             WaveParameters {
@@ -68,21 +248,46 @@ impl Default for WaterWaves {
                 direction: Vec2::new(0.8, 0.3).normalize(),
                 steepness: 0.15,
                 wave_number: 2.0 * std::f32::consts::PI / 8.0,
+                phase: 0.0,
             },
         ];
 
-        // Validate steepness to prevent over-steep waves (Q * A * k should be < 1.0)
-        for wave in &mut waves {
-            let max_steepness = 0.9 / (wave.amplitude * wave.wave_number);
-            if wave.steepness > max_steepness {
-                wave.steepness = max_steepness;
-            }
-        }
+        clamp_wave_steepness(&mut waves);
 
         Self { waves }
     }
 }
 
+impl WaterWaves {
+    /// Build a wave set by sampling discrete Gerstner components from a physical sea state spectrum
+    pub fn from_spectrum(spectrum: &OceanSpectrum, sampling: &SpectrumSampling) -> Self {
+        Self {
+            waves: sample_spectrum(spectrum, sampling),
+        }
+    }
+
+    /// Superimpose several independently sampled sea states, e.g. a swell crossing a local wind sea.
+    /// Each sea state shares its own `steepness` between its components, so keep their sum below 1.0.
+    pub fn from_sea_states(sea_states: &[(OceanSpectrum, SpectrumSampling)]) -> Self {
+        Self {
+            waves: sea_states
+                .iter()
+                .flat_map(|(spectrum, sampling)| sample_spectrum(spectrum, sampling))
+                .collect(),
+        }
+    }
+}
+
+/// Validate steepness to prevent over-steep waves (Q * A * k should be < 1.0)
+pub fn clamp_wave_steepness(waves: &mut [WaveParameters]) {
+    for wave in waves {
+        let max_steepness = 0.9 / (wave.amplitude * wave.wave_number);
+        if wave.steepness > max_steepness {
+            wave.steepness = max_steepness;
+        }
+    }
+}
+
 pub fn create_water_mesh(grid_size: usize, world_size: f32) -> (Mesh, Vec<Vec3>) {
     let vertex_count = grid_size * grid_size;
     let mut positions = Vec::with_capacity(vertex_count);
@@ -136,9 +341,9 @@ pub fn create_water_mesh(grid_size: usize, world_size: f32) -> (Mesh, Vec<Vec3>)
 
 /// Calculate Gerstner wave displacement at a given position and time
 /// Returns (horizontal_x, horizontal_z, vertical_y) displacement
-fn calculate_gerstner_displacement(position: Vec2, wave: &WaveParameters, time: f32) -> (f32, f32, f32) {
+pub fn calculate_gerstner_displacement(position: Vec2, wave: &WaveParameters, time: f32) -> (f32, f32, f32) {
     let dot_product = position.dot(wave.direction);
-    let phase = wave.wave_number * dot_product - wave.speed * time;
+    let phase = wave.wave_number * dot_product - wave.speed * time + wave.phase;
     let cos_phase = phase.cos();
     let sin_phase = phase.sin();
     
@@ -153,40 +358,6 @@ fn calculate_gerstner_displacement(position: Vec2, wave: &WaveParameters, time:
     (horizontal_x, horizontal_z, vertical_y)
 }
 
-/// SIMD-optimized Gerstner wave calculation for 4 positions at once
-fn calculate_gerstner_displacement_simd(
-    positions_x: f32x4,
-    positions_z: f32x4,
-    wave: &WaveParameters,
-    time: f32,
-) -> (f32x4, f32x4, f32x4) {
-    // Calculate dot products for 4 positions
-    let dir_x = f32x4::splat(wave.direction.x);
-    let dir_z = f32x4::splat(wave.direction.y);
-    let dot_products = positions_x * dir_x + positions_z * dir_z;
-    
-    // Calculate phases
-    let wave_number = f32x4::splat(wave.wave_number);
-    let speed_time = f32x4::splat(wave.speed * time);
-    let phases = wave_number * dot_products - speed_time;
-    
-    // Calculate sin and cos of phases
-    let sin_phases = phases.sin();
-    let cos_phases = phases.cos();
-    
-    // Horizontal displacement
-    let q_a = f32x4::splat(wave.steepness * wave.amplitude);
-    let q_a_cos = q_a * cos_phases;
-    let horizontal_x = q_a_cos * dir_x;
-    let horizontal_z = q_a_cos * dir_z;
-    
-    // Vertical displacement
-    let amplitude = f32x4::splat(wave.amplitude);
-    let vertical_y = amplitude * sin_phases;
-    
-    (horizontal_x, horizontal_z, vertical_y)
-}
-
 /// Fast height-only query for Gerstner waves (for surfboard physics)
 /// This skips horizontal displacement calculation when only height is needed
 pub fn get_wave_height(position: Vec2, waves: &[WaveParameters], time: f32) -> f32 {
@@ -194,7 +365,7 @@ pub fn get_wave_height(position: Vec2, waves: &[WaveParameters], time: f32) -> f
     
     for wave in waves {
         let dot_product = position.dot(wave.direction);
-        let phase = wave.wave_number * dot_product - wave.speed * time;
+        let phase = wave.wave_number * dot_product - wave.speed * time + wave.phase;
         total_height += wave.amplitude * phase.sin();
     }
     
@@ -207,90 +378,819 @@ pub fn query_wave_height_at_time(position: Vec2, waves: &[WaveParameters], time:
     get_wave_height(position, waves, time)
 }
 
+/// Total Gerstner displacement (x, height, z) of the grid point at `position`
+pub fn get_wave_displacement(position: Vec2, waves: &[WaveParameters], time: f32) -> Vec3 {
+    let mut total_displacement = Vec3::ZERO;
+    
+    for wave in waves {
+        let (dx, dz, dy) = calculate_gerstner_displacement(position, wave, time);
+        total_displacement += Vec3::new(dx, dy, dz);
+    }
+    
+    total_displacement
+}
+
+/// Iteration limits for `get_displaced_wave_height`
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct HeightQuerySettings {
+    pub max_iterations: u32,
+    pub tolerance: f32, // Stop once the horizontal error is below this many meters
+}
+
+impl Default for HeightQuerySettings {
+    fn default() -> Self {
+        Self {
+            max_iterations: 8,
+            tolerance: 1e-4,
+        }
+    }
+}
+
+/// Find the undisplaced grid position whose Gerstner displacement lands on `target`
+///
+/// Solves p + D(p) = target with Newton iterations, starting from p = target. The Jacobian
+/// I + dD/dp stays invertible as long as the summed steepness keeps crests from looping over.
+pub fn find_undisplaced_position(
+    target: Vec2,
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+) -> Vec2 {
+    let mut position = target;
+    
+    for _ in 0..settings.max_iterations {
+        let mut displaced = position;
+        let mut jacobian = Mat2::IDENTITY;
+        
+        for wave in waves {
+            let dot_product = position.dot(wave.direction);
+            let phase = wave.wave_number * dot_product - wave.speed * time + wave.phase;
+            let (sin_phase, cos_phase) = phase.sin_cos();
+            
+            let q_a = wave.steepness * wave.amplitude;
+            displaced += wave.direction * (q_a * cos_phase);
+            
+            // d/dp of Q*A*d*cos(phase) = -Q*A*k * sin(phase) * d d^T
+            let outer = Mat2::from_cols(wave.direction * wave.direction.x, wave.direction * wave.direction.y);
+            jacobian -= outer * (q_a * wave.wave_number * sin_phase);
+        }
+        
+        let error = displaced - target;
+        if error.length() < settings.tolerance {
+            break;
+        }
+        
+        let determinant = jacobian.determinant();
+        position -= if determinant.abs() > 1e-3 {
+            jacobian.inverse() * error
+        } else {
+            // Near a folded crest, fall back to a fixed-point step
+            error
+        };
+    }
+    
+    position
+}
+
+/// Height of the rendered (horizontally displaced) Gerstner surface directly above `position`
+///
+/// Unlike `get_wave_height`, which evaluates the vertical offset of the grid point that started at
+/// `position`, this finds the grid point that was pushed to `position` and returns its height.
+pub fn get_displaced_wave_height(
+    position: Vec2,
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+) -> f32 {
+    let undisplaced = find_undisplaced_position(position, waves, time, settings);
+    get_wave_height(undisplaced, waves, time)
+}
+
+/// Surface state at one point, for physics that needs more than a height
+#[derive(Debug, Clone, Copy)]
+pub struct WaterSample {
+    pub position: Vec3,     // Displaced surface point
+    pub normal: Vec3,       // Unit surface normal
+    pub gradient: Vec2,     // Surface slope (dh/dx, dh/dz) in world space
+    pub velocity: Vec3,     // Orbital velocity of the water particle on the surface
+    pub acceleration: Vec3, // Orbital acceleration of the water particle on the surface
+}
+
+/// Running sums of the Gerstner waves at a set of grid points: the displacement, its partial derivatives
+/// (GPU Gems 1, ch. 1) and optionally the orbital velocity. Every surface evaluation builds on these, with
+/// `f32` for one point or a SIMD type for a lane per point.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GerstnerSums<S> {
+    pub displacement: [S; 3],
+    pub tangent_x: [S; 3],   // dP/dx
+    pub tangent_z_yz: [S; 2], // y and z of dP/dz; its x equals dP/dx's z, as both come from the symmetric d d^T
+    pub velocity: [S; 3],
+}
+
+impl<S: SimdF32> GerstnerSums<S> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        let (zero, one) = (S::splat(0.0), S::splat(1.0));
+        Self {
+            displacement: [zero; 3],
+            tangent_x: [one, zero, zero],
+            tangent_z_yz: [zero, one],
+            velocity: [zero; 3],
+        }
+    }
+
+    /// Add the displacement of `wave`, given the sine and cosine of its phase
+    #[inline(always)]
+    pub fn add_displacement(&mut self, wave: &WaveParameters, sin_phase: S, cos_phase: S) {
+        let q_a_cos = S::splat(wave.steepness * wave.amplitude) * cos_phase;
+        self.displacement[0] += q_a_cos * S::splat(wave.direction.x);
+        self.displacement[1] += S::splat(wave.amplitude) * sin_phase;
+        self.displacement[2] += q_a_cos * S::splat(wave.direction.y);
+    }
+
+    /// Add the displacement and derivatives of `wave`, given the sine and cosine of its phase
+    #[inline(always)]
+    pub fn add_wave(&mut self, wave: &WaveParameters, sin_phase: S, cos_phase: S) {
+        self.add_displacement(wave, sin_phase, cos_phase);
+        
+        // d/dp of the displacement: horizontal -Q*A*k*sin * d d^T, vertical A*k*cos * d
+        let dir_x = S::splat(wave.direction.x);
+        let dir_z = S::splat(wave.direction.y);
+        let q_a_k_sin = S::splat(wave.steepness * wave.amplitude * wave.wave_number) * sin_phase;
+        let a_k_cos = S::splat(wave.amplitude * wave.wave_number) * cos_phase;
+        self.tangent_x[0] -= q_a_k_sin * dir_x * dir_x;
+        self.tangent_x[1] += a_k_cos * dir_x;
+        self.tangent_x[2] -= q_a_k_sin * dir_x * dir_z;
+        self.tangent_z_yz[0] += a_k_cos * dir_z;
+        self.tangent_z_yz[1] -= q_a_k_sin * dir_z * dir_z;
+    }
+
+    /// Add the orbital velocity of `wave`, the time derivative of its displacement
+    #[inline(always)]
+    pub fn add_velocity(&mut self, wave: &WaveParameters, sin_phase: S, cos_phase: S) {
+        // Phase decreases with time, so d/dt sin = -ω cos and d/dt cos = ω sin
+        let q_a_omega_sin = S::splat(wave.steepness * wave.amplitude * wave.speed) * sin_phase;
+        self.velocity[0] += q_a_omega_sin * S::splat(wave.direction.x);
+        self.velocity[1] -= S::splat(wave.amplitude * wave.speed) * cos_phase;
+        self.velocity[2] += q_a_omega_sin * S::splat(wave.direction.y);
+    }
+
+    /// dP/dz x dP/dx, not normalised
+    #[inline(always)]
+    pub fn normal(&self) -> [S; 3] {
+        let [tangent_x_x, tangent_x_y, tangent_x_z] = self.tangent_x;
+        let [tangent_z_y, tangent_z_z] = self.tangent_z_yz;
+        [
+            tangent_z_y * tangent_x_z - tangent_z_z * tangent_x_y,
+            tangent_z_z * tangent_x_x - tangent_x_z * tangent_x_z,
+            tangent_x_z * tangent_x_y - tangent_z_y * tangent_x_x,
+        ]
+    }
+}
+
+/// Evaluate the Gerstner surface for the grid point that started at `grid_position`
+///
+/// Normal and gradient come from the analytic partial derivatives of the displaced position
+/// (GPU Gems 1, ch. 1); velocity and acceleration are its first and second time derivatives.
+pub fn sample_gerstner_surface(grid_position: Vec2, waves: &[WaveParameters], time: f32) -> WaterSample {
+    let mut sums = GerstnerSums::<f32>::new();
+    let mut acceleration = Vec3::ZERO;
+
+    for wave in waves {
+        let (sin_phase, cos_phase) = wave_phase(grid_position.x, grid_position.y, wave, time).sin_cos();
+        sums.add_wave(wave, sin_phase, cos_phase);
+        sums.add_velocity(wave, sin_phase, cos_phase);
+
+        // The second time derivative of each wave's displacement is -ω² times the displacement
+        let direction = Vec3::new(wave.direction.x, 0.0, wave.direction.y);
+        let q_a_cos = wave.steepness * wave.amplitude * cos_phase;
+        let displacement = direction * q_a_cos + Vec3::Y * (wave.amplitude * sin_phase);
+        acceleration -= displacement * (wave.speed * wave.speed);
+    }
+
+    let normal = Vec3::from_array(sums.normal()).normalize_or(Vec3::Y);
+    let gradient = Vec2::new(-normal.x, -normal.z) / normal.y.max(1e-6);
+
+    WaterSample {
+        position: Vec3::new(grid_position.x, 0.0, grid_position.y) + Vec3::from_array(sums.displacement),
+        normal,
+        gradient,
+        velocity: Vec3::from_array(sums.velocity),
+        acceleration,
+    }
+}
+
+/// Sample the rendered surface at world XZ `position`, inverting the horizontal displacement first
+pub fn sample_water(
+    position: Vec2,
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+) -> WaterSample {
+    let undisplaced = find_undisplaced_position(position, waves, time, settings);
+    sample_gerstner_surface(undisplaced, waves, time)
+}
+
+/// Orbital velocity `depth` meters below the still water level under world XZ `position`
+/// Each wave's contribution decays as e^(-k * depth), as in deep water linear wave theory
+pub fn sample_water_velocity_at_depth(
+    position: Vec2,
+    depth: f32,
+    waves: &[WaveParameters],
+    time: f32,
+    settings: &HeightQuerySettings,
+) -> Vec3 {
+    let undisplaced = find_undisplaced_position(position, waves, time, settings);
+    let mut velocity = Vec3::ZERO;
+
+    for wave in waves {
+        let dot_product = undisplaced.dot(wave.direction);
+        let phase = wave.wave_number * dot_product - wave.speed * time + wave.phase;
+        let (sin_phase, cos_phase) = phase.sin_cos();
+
+        let decay = (-wave.wave_number * depth.max(0.0)).exp();
+        let direction = Vec3::new(wave.direction.x, 0.0, wave.direction.y);
+        velocity += (direction * (wave.steepness * sin_phase) - Vec3::Y * cos_phase)
+            * (wave.amplitude * wave.speed * decay);
+    }
+
+    velocity
+}
+
+/// Write the Gerstner-displaced position of every base vertex into `pos_data`
+/// This is exactly the surface `update_water_vertices` renders
+pub fn displace_water_vertices(
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    time: f32,
+    pos_data: &mut [[f32; 3]],
+) {
+    displace_water_vertices_with_lanes(LaneWidth::detect(), base_positions, waves, time, pos_data);
+}
+
+/// `displace_water_vertices` at a fixed lane width instead of the widest one the CPU supports
+pub fn displace_water_vertices_with_lanes(
+    lane_width: LaneWidth,
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    time: f32,
+    pos_data: &mut [[f32; 3]],
+) {
+    displace_surface_range_with_lanes(
+        lane_width,
+        base_positions,
+        0,
+        waves,
+        time,
+        Phases::Evaluate(SinCosPrecision::Exact),
+        pos_data,
+        &mut [],
+        &mut [],
+    );
+}
+
+/// `displace_water_vertices` that also writes the analytic unit normal and tangent (dP/dx, with the
+/// bitangent sign in w) of every vertex, from the partial derivatives `sample_gerstner_surface` uses
+pub fn displace_water_surface(
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    time: f32,
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+) {
+    displace_water_surface_with_precision(
+        SinCosPrecision::Exact,
+        base_positions,
+        waves,
+        time,
+        pos_data,
+        normal_data,
+        tangent_data,
+    );
+}
+
+/// `displace_water_surface` with approximate sines and cosines, see `SinCosPrecision`
+pub fn displace_water_surface_with_precision(
+    precision: SinCosPrecision,
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    time: f32,
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+) {
+    displace_surface_range_with_lanes(
+        LaneWidth::detect(),
+        base_positions,
+        0,
+        waves,
+        time,
+        Phases::Evaluate(precision),
+        pos_data,
+        normal_data,
+        tangent_data,
+    );
+}
+
+/// `displace_water_surface` with the sine and cosine of every wave phase read from `phasors`, which have to
+/// be advanced to the current time for these base positions and waves
+pub fn displace_water_surface_from_phasors(
+    phasors: &WavePhasors,
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+) {
+    assert!(phasors.matches(base_positions, waves), "phasors are out of date for these waves");
+    displace_surface_range_with_lanes(
+        LaneWidth::detect(),
+        base_positions,
+        0,
+        waves,
+        phasors.time,
+        Phases::Rotated(phasors),
+        pos_data,
+        normal_data,
+        tangent_data,
+    );
+}
+
+/// `displace_water_surface` at a fixed lane width instead of the widest one the CPU supports
+pub fn displace_water_surface_with_lanes(
+    lane_width: LaneWidth,
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    time: f32,
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+) {
+    displace_surface_range_with_lanes(
+        lane_width,
+        base_positions,
+        0,
+        waves,
+        time,
+        Phases::Evaluate(SinCosPrecision::Exact),
+        pos_data,
+        normal_data,
+        tangent_data,
+    );
+}
+
+lane_dispatch! {
+    /// Displace the vertices from `first` on, one per element of the output slices. Empty normal and
+    /// tangent slices skip the derivatives and only write positions.
+    fn displace_surface_range_with_lanes => displace_surface_lanes(
+        base_positions: &BasePositions,
+        first: usize,
+        waves: &[WaveParameters],
+        time: f32,
+        phases: Phases<'_>,
+        pos_data: &mut [[f32; 3]],
+        normal_data: &mut [[f32; 3]],
+        tangent_data: &mut [[f32; 4]],
+    )
+}
+
+/// Where the surface kernel takes the sine and cosine of every wave phase from
+#[derive(Clone, Copy)]
+enum Phases<'a> {
+    Evaluate(SinCosPrecision),
+    Rotated(&'a WavePhasors),
+}
+
+#[inline(always)]
+fn displace_surface_lanes<S: SimdF32>(
+    base_positions: &BasePositions,
+    first: usize,
+    waves: &[WaveParameters],
+    time: f32,
+    phases: Phases<'_>,
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+) {
+    let lanes = S::LANES;
+    let wave_dots = base_positions.wave_dots(waves);
+    let padded_len = base_positions.padded_len();
+    let frames = !normal_data.is_empty() || !tangent_data.is_empty();
+    let mut count = pos_data.len().min(base_positions.len().saturating_sub(first));
+    if frames {
+        count = count.min(normal_data.len()).min(tangent_data.len());
+    }
+    
+    for (chunk, pos_chunk) in pos_data[..count].chunks_mut(lanes).enumerate() {
+        let start = first + chunk * lanes;
+        let positions_x = S::from_slice(&base_positions.x()[start..]);
+        let positions_z = S::from_slice(&base_positions.z()[start..]);
+        
+        let mut sums = GerstnerSums::<S>::new();
+        for (index, wave) in waves.iter().enumerate() {
+            let offset = index * padded_len + start;
+            let (sin_phases, cos_phases) = match phases {
+                Phases::Evaluate(precision) => {
+                    let dots = wave_dots.map(|dots| &dots[offset..]);
+                    sin_cos(lane_phases(dots, positions_x, positions_z, wave, time), precision)
+                }
+                Phases::Rotated(phasors) => {
+                    (S::from_slice(&phasors.sin()[offset..]), S::from_slice(&phasors.cos()[offset..]))
+                }
+            };
+            if frames {
+                sums.add_wave(wave, sin_phases, cos_phases);
+            } else {
+                sums.add_displacement(wave, sin_phases, cos_phases);
+            }
+        }
+        
+        let [total_dx, total_dy, total_dz] = sums.displacement;
+        let [x, dx, dy, z, dz] = store_lanes([positions_x, total_dx, total_dy, positions_z, total_dz]);
+        for (lane, pos) in pos_chunk.iter_mut().enumerate() {
+            *pos = [x[lane] + dx[lane], dy[lane], z[lane] + dz[lane]];
+        }
+        if !frames {
+            continue;
+        }
+        
+        let [normal_x, normal_y, normal_z] = sums.normal();
+        let [tangent_x_x, tangent_x_y, tangent_x_z] = sums.tangent_x;
+        let normal_length = (normal_x * normal_x + normal_y * normal_y + normal_z * normal_z).sqrt();
+        let tangent_length =
+            (tangent_x_x * tangent_x_x + tangent_x_y * tangent_x_y + tangent_x_z * tangent_x_z).sqrt();
+        
+        let [n_x, n_y, n_z] =
+            store_lanes([normal_x / normal_length, normal_y / normal_length, normal_z / normal_length]);
+        let [t_x, t_y, t_z] =
+            store_lanes([tangent_x_x / tangent_length, tangent_x_y / tangent_length, tangent_x_z / tangent_length]);
+        
+        let range = chunk * lanes..chunk * lanes + pos_chunk.len();
+        let outputs = normal_data[range.clone()].iter_mut().zip(&mut tangent_data[range]);
+        for (lane, (normal, tangent)) in outputs.enumerate() {
+            *normal = [n_x[lane], n_y[lane], n_z[lane]];
+            // UVs grow along +x and +z, so the bitangent n x t (= -dP/dz) needs flipping
+            *tangent = [t_x[lane], t_y[lane], t_z[lane], -1.0];
+        }
+    }
+}
+
+/// `wave_phase` of the lanes in `positions_x` and `positions_z`, or just the time term taken off the
+/// cached k (d · p) starting at `dots` when the base positions have them
+#[inline(always)]
+fn lane_phases<S: SimdF32>(
+    dots: Option<&[f32]>,
+    positions_x: S,
+    positions_z: S,
+    wave: &WaveParameters,
+    time: f32,
+) -> S {
+    match dots {
+        Some(dots) => S::from_slice(dots) - S::splat(wave.speed * time - wave.phase),
+        None => wave_phase(positions_x, positions_z, wave, time),
+    }
+}
+
+#[inline(always)]
+fn store_lanes<S: SimdF32, const N: usize>(values: [S; N]) -> [[f32; MAX_LANES]; N] {
+    values.map(|value| {
+        let mut lanes = [0.0; MAX_LANES];
+        value.store(&mut lanes[..S::LANES]);
+        lanes
+    })
+}
+
+/// How `update_water_vertices` splits the displacement work across `ComputeTaskPool`
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct WaterDisplacementSettings {
+    // Vertices per task, rounded up to whole SIMD chunks. A batch covering the whole mesh runs on
+    // the calling thread. Multiples of the grid row length keep each task on whole rows.
+    pub batch_size: usize,
+    pub cache_wave_dots: bool, // Keep k (d · p) per wave and vertex around, see `BasePositions::cache_wave_dots`
+    pub sin_cos_precision: SinCosPrecision, // Trade surface accuracy for speed in the rendered mesh
+}
+
+impl Default for WaterDisplacementSettings {
+    fn default() -> Self {
+        Self {
+            batch_size: 2048,
+            cache_wave_dots: false,
+            sin_cos_precision: SinCosPrecision::Exact,
+        }
+    }
+}
+
+/// Sine and cosine of every wave phase at every vertex, carried from tick to tick by rotating them through
+/// -ω Δt instead of evaluating sin and cos again. Add it to a `WaterSurface` entity whose waves stay the same
+/// for long stretches; `update_water_vertices` then displaces that surface from the rotated phasors.
+#[derive(Component, Debug, Clone)]
+pub struct WavePhasors {
+    pub resync_interval: u32, // Ticks between exact re-evaluations that clear the rotations' rounding drift
+    sin: Vec<LaneBlock>,
+    cos: Vec<LaneBlock>,
+    wave_keys: Vec<[f32; 5]>, // Everything about each wave the phase depends on
+    base_generation: Option<u32>,
+    time: f32,
+    ticks: u32, // Rotations since the last exact evaluation
+}
+
+impl Default for WavePhasors {
+    fn default() -> Self {
+        Self::new(240)
+    }
+}
+
+impl WavePhasors {
+    pub fn new(resync_interval: u32) -> Self {
+        Self {
+            resync_interval,
+            sin: Vec::new(),
+            cos: Vec::new(),
+            wave_keys: Vec::new(),
+            base_generation: None,
+            time: 0.0,
+            ticks: 0,
+        }
+    }
+
+    /// Bring the phasors from their last time to `time` by rotating them through -ω `dt`. Every
+    /// `resync_interval` ticks, whenever the waves or base positions changed, and when `dt` does not
+    /// lead from the last time to `time` (e.g. a tile skipped while culled), they are evaluated exactly instead.
+    pub fn advance(&mut self, base_positions: &BasePositions, waves: &[WaveParameters], time: f32, dt: f32) {
+        let skipped = (time - self.time - dt).abs() > 0.5 * dt.abs();
+        if skipped || !self.matches(base_positions, waves) || self.ticks >= self.resync_interval {
+            self.resync(base_positions, waves, time);
+            return;
+        }
+
+        let padded_len = base_positions.padded_len().max(1);
+        let sin = lanes_mut(&mut self.sin).chunks_mut(padded_len);
+        let cos = lanes_mut(&mut self.cos).chunks_mut(padded_len);
+        for (wave, (sin, cos)) in waves.iter().zip(sin.zip(cos)) {
+            let (sin_step, cos_step) = (-wave.speed * dt).sin_cos();
+            for (sin, cos) in sin.iter_mut().zip(cos) {
+                let rotated_sin = *sin * cos_step + *cos * sin_step;
+                let rotated_cos = *cos * cos_step - *sin * sin_step;
+                // One Newton step towards unit length keeps the magnitude from drifting between resyncs
+                let renormalize = 1.5 - 0.5 * (rotated_sin * rotated_sin + rotated_cos * rotated_cos);
+                *sin = rotated_sin * renormalize;
+                *cos = rotated_cos * renormalize;
+            }
+        }
+        self.time = time;
+        self.ticks += 1;
+    }
+
+    /// Evaluate every phasor exactly at `time`
+    pub fn resync(&mut self, base_positions: &BasePositions, waves: &[WaveParameters], time: f32) {
+        let padded_len = base_positions.padded_len();
+        let blocks = waves.len() * padded_len / MAX_LANES;
+        self.sin.resize(blocks, LaneBlock::default());
+        self.cos.resize(blocks, LaneBlock::default());
+        self.wave_keys.clear();
+        self.wave_keys.extend(waves.iter().map(phase_key));
+        self.base_generation = Some(base_positions.generation);
+        self.time = time;
+        self.ticks = 0;
+
+        let wave_dots = base_positions.wave_dots(waves);
+        let sin = lanes_mut(&mut self.sin);
+        let cos = lanes_mut(&mut self.cos);
+        for (index, wave) in waves.iter().enumerate() {
+            for start in (0..padded_len).step_by(f32x8::LANES) {
+                let offset = index * padded_len + start;
+                let dots = wave_dots.map(|dots| &dots[offset..]);
+                let positions_x = f32x8::from_slice(&base_positions.x()[start..]);
+                let positions_z = f32x8::from_slice(&base_positions.z()[start..]);
+                let (sin_phases, cos_phases) = lane_phases(dots, positions_x, positions_z, wave, time).sin_cos();
+                sin_phases.store(&mut sin[offset..offset + f32x8::LANES]);
+                cos_phases.store(&mut cos[offset..offset + f32x8::LANES]);
+            }
+        }
+    }
+
+    /// Whether the phasors were last evaluated for these base positions and waves
+    pub fn matches(&self, base_positions: &BasePositions, waves: &[WaveParameters]) -> bool {
+        self.base_generation == Some(base_positions.generation)
+            && self.wave_keys.len() == waves.len()
+            && self.wave_keys.iter().copied().eq(waves.iter().map(phase_key))
+    }
+
+    /// Time the phasors were last advanced to
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Sine of every wave phase, one run of `BasePositions::padded_len` lanes per wave
+    pub fn sin(&self) -> &[f32] {
+        lanes(&self.sin)
+    }
+
+    /// Cosine of every wave phase, laid out like `sin`
+    pub fn cos(&self) -> &[f32] {
+        lanes(&self.cos)
+    }
+}
+
+fn phase_key(wave: &WaveParameters) -> [f32; 5] {
+    [wave.direction.x, wave.direction.y, wave.wave_number, wave.speed, wave.phase]
+}
+
+/// `displace_water_surface_with_precision`, or `displace_water_surface_from_phasors` when given `phasors`, split
+/// into batches of `settings.batch_size` vertices that run on `ComputeTaskPool`. Batches start on `MAX_LANES`
+/// boundaries, so the output is identical to the single-threaded call.
+pub fn displace_water_surface_parallel(
+    base_positions: &BasePositions,
+    waves: &[WaveParameters],
+    time: f32,
+    pos_data: &mut [[f32; 3]],
+    normal_data: &mut [[f32; 3]],
+    tangent_data: &mut [[f32; 4]],
+    phasors: Option<&WavePhasors>,
+    settings: &WaterDisplacementSettings,
+) {
+    let phases = match phasors {
+        Some(phasors) => {
+            assert!(phasors.matches(base_positions, waves), "phasors are out of date for these waves");
+            Phases::Rotated(phasors)
+        }
+        None => Phases::Evaluate(settings.sin_cos_precision),
+    };
+    let batch_size = settings.batch_size.max(1).next_multiple_of(MAX_LANES);
+    let lane_width = LaneWidth::detect();
+    if base_positions.len() <= batch_size {
+        displace_surface_range_with_lanes(
+            lane_width,
+            base_positions,
+            0,
+            waves,
+            time,
+            phases,
+            pos_data,
+            normal_data,
+            tangent_data,
+        );
+        return;
+    }
+    
+    let batches = pos_data
+        .chunks_mut(batch_size)
+        .zip(normal_data.chunks_mut(batch_size))
+        .zip(tangent_data.chunks_mut(batch_size))
+        .enumerate();
+    
+    ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+        for (batch, ((pos_data, normal_data), tangent_data)) in batches {
+            scope.spawn(async move {
+                displace_surface_range_with_lanes(
+                    lane_width,
+                    base_positions,
+                    batch * batch_size,
+                    waves,
+                    time,
+                    phases,
+                    pos_data,
+                    normal_data,
+                    tangent_data,
+                );
+            });
+        }
+    });
+}
+
 pub fn update_water_vertices(
     time: Res<Time>,
+    settings: Res<WaterDisplacementSettings>,
     mut meshes: ResMut<Assets<Mesh>>,
-    query: Query<(&Mesh3d, &WaterSurface, &WaterWaves)>,
+    mut surfaces: Query<(
+        Entity,
+        &Mesh3d,
+        &mut WaterSurface,
+        Option<&mut WavePhasors>,
+        Option<&WaterTile>,
+        Option<&ChildOf>,
+    )>,
+    water: Query<AnyOf<(&WaterWaves, &FftOcean)>>,
 ) {
     let elapsed = time.elapsed_secs();
+    let dt = time.delta_secs();
     
-    for (mesh_3d, surface, waves) in query.iter() {
-        if let Some(mesh) = meshes.get_mut(&mesh_3d.0) {
-            if let Some(positions) = mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
-                if let bevy::render::mesh::VertexAttributeValues::Float32x3(pos_data) = positions {
-                    // Process vertices in chunks of 4 for SIMD optimization
-                    let base_positions = &surface.base_positions;
-                    let vertex_count = base_positions.len();
-                    
-                    // Process SIMD chunks (4 vertices at a time)
-                    for chunk_start in (0..vertex_count).step_by(4) {
-                        let chunk_end = (chunk_start + 4).min(vertex_count);
-                        
-                        if chunk_end - chunk_start == 4 {
-                            // Full SIMD chunk - process 4 vertices at once
-                            let positions_x = f32x4::new([
-                                base_positions[chunk_start].x,
-                                base_positions[chunk_start + 1].x,
-                                base_positions[chunk_start + 2].x,
-                                base_positions[chunk_start + 3].x,
-                            ]);
-                            let positions_z = f32x4::new([
-                                base_positions[chunk_start].z,
-                                base_positions[chunk_start + 1].z,
-                                base_positions[chunk_start + 2].z,
-                                base_positions[chunk_start + 3].z,
-                            ]);
-                            
-                            // Accumulate displacements from all waves
-                            let mut total_dx = f32x4::splat(0.0);
-                            let mut total_dz = f32x4::splat(0.0);
-                            let mut total_dy = f32x4::splat(0.0);
-                            
-                            for wave in &waves.waves {
-                                let (dx, dz, dy) = calculate_gerstner_displacement_simd(
-                                    positions_x, positions_z, wave, elapsed
-                                );
-                                total_dx += dx;
-                                total_dz += dz;
-                                total_dy += dy;
-                            }
-                            
-                            // Apply displacements to vertices
-                            let dx_array: [f32; 4] = total_dx.to_array();
-                            let dz_array: [f32; 4] = total_dz.to_array();
-                            let dy_array: [f32; 4] = total_dy.to_array();
-                            
-                            for i in 0..4 {
-                                let idx = chunk_start + i;
-                                pos_data[idx][0] = base_positions[idx].x + dx_array[i];
-                                pos_data[idx][1] = dy_array[i];
-                                pos_data[idx][2] = base_positions[idx].z + dz_array[i];
-                            }
-                        } else {
-                            // Handle remaining vertices with scalar calculation
-                            for idx in chunk_start..chunk_end {
-                                let base_pos = &base_positions[idx];
-                                let pos_2d = Vec2::new(base_pos.x, base_pos.z);
-                                
-                                let mut total_displacement = (0.0f32, 0.0f32, 0.0f32);
-                                for wave in &waves.waves {
-                                    let (dx, dz, dy) = calculate_gerstner_displacement(pos_2d, wave, elapsed);
-                                    total_displacement.0 += dx;
-                                    total_displacement.1 += dz;
-                                    total_displacement.2 += dy;
-                                }
-                                
-                                pos_data[idx][0] = base_pos.x + total_displacement.0;
-                                pos_data[idx][1] = total_displacement.2;
-                                pos_data[idx][2] = base_pos.z + total_displacement.1;
-                            }
-                        }
-                    }
-                }
+    for (entity, mesh_3d, mut surface, mut phasors, tile, child_of) in surfaces.iter_mut() {
+        // Tiles take their waves from the water entity they belong to and sit still while off screen
+        if tile.is_some_and(|tile| !tile.visible) {
+            continue;
+        }
+        let source = tile.and(child_of).map_or(entity, |child_of| child_of.parent());
+        let Ok((waves, fft_ocean)) = water.get(source) else {
+            continue;
+        };
+        
+        // An FFT-only surface still runs the Gerstner loop below, which then just resets to base positions
+        let waves: &[WaveParameters] = waves.map_or(&[], |waves| &waves.waves);
+        
+        let Some(mesh) = meshes.get_mut(&mesh_3d.0) else {
+            continue;
+        };
+        // Only touched mutably to rebuild the cache, so surfaces are not flagged as changed every tick
+        if settings.cache_wave_dots && surface.base_positions.wave_dots(waves).is_none() {
+            surface.base_positions.cache_wave_dots(waves);
+        }
+        let surface = &*surface;
+        let base_positions = &surface.base_positions;
+        if let Some(phasors) = &mut phasors {
+            phasors.advance(base_positions, waves, elapsed, dt);
+        }
+        
+        // Only the first frame after a mesh is built allocates; after that every buffer is written in place
+        if !mesh.contains_attribute(Mesh::ATTRIBUTE_NORMAL) {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; base_positions.len()]);
+        }
+        if !mesh.contains_attribute(Mesh::ATTRIBUTE_TANGENT) {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vec![[1.0, 0.0, 0.0, -1.0]; base_positions.len()]);
+        }
+        
+        // Borrowed together so positions, normals and tangents can be written in the same pass
+        let (mut pos_data, mut normals, mut tangents) = (None, None, None);
+        for (attribute, values) in mesh.attributes_mut() {
+            if attribute.id == Mesh::ATTRIBUTE_POSITION.id
+                && let VertexAttributeValues::Float32x3(values) = values
+            {
+                pos_data = Some(values);
+            } else if attribute.id == Mesh::ATTRIBUTE_NORMAL.id
+                && let VertexAttributeValues::Float32x3(values) = values
+            {
+                normals = Some(values);
+            } else if attribute.id == Mesh::ATTRIBUTE_TANGENT.id
+                && let VertexAttributeValues::Float32x4(values) = values
+            {
+                tangents = Some(values);
+            }
+        }
+        let (Some(pos_data), Some(normals), Some(tangents)) = (pos_data, normals, tangents) else {
+            continue;
+        };
+        normals.resize(base_positions.len(), [0.0, 1.0, 0.0]);
+        tangents.resize(base_positions.len(), [1.0, 0.0, 0.0, -1.0]);
+        
+        displace_water_surface_parallel(
+            base_positions,
+            waves,
+            elapsed,
+            pos_data,
+            normals,
+            tangents,
+            phasors.as_deref(),
+            &settings,
+        );
+        
+        // Layer the FFT ocean displacement and slope on top of the Gerstner sum. Tangents are turned with the
+        // normals so normal maps keep shading with a consistent basis.
+        if let Some(fft_ocean) = fft_ocean {
+            let vertices = pos_data.iter_mut().zip(normals.iter_mut()).zip(tangents.iter_mut());
+            for (((vertex, normal), tangent), base_pos) in vertices.zip(base_positions.iter()) {
+                let local_xz = Vec2::new(base_pos.x, base_pos.z);
+                let displacement = fft_ocean.displacement_at(local_xz);
+                vertex[0] += displacement.x;
+                vertex[1] += displacement.y;
+                vertex[2] += displacement.z;
+                
+                let gerstner = Vec3::from(*normal);
+                let slope = Vec2::new(-gerstner.x, -gerstner.z) / gerstner.y.max(1e-6);
+                let gradient = slope + fft_ocean.gradient_at(local_xz);
+                let tilted = Vec3::new(-gradient.x, 1.0, -gradient.y).normalize();
+                let along = Vec3::new(tangent[0], tangent[1], tangent[2]);
+                let along = along.reject_from_normalized(tilted).normalize_or(Vec3::X);
+                *normal = tilted.to_array();
+                [tangent[0], tangent[1], tangent[2]] = along.to_array();
             }
-            
-            // Recompute normals for proper lighting with the new geometry
-            mesh.compute_normals();
+        }
+        
+        stitch_seams(&surface.seams, pos_data, normals, tangents);
+    }
+}
+
+/// Where and how large `spawn_water` makes the water surface.
+/// Insert before the `WaterPlugin` startup systems run, e.g. to place a lake at 50 m elevation.
+#[derive(Resource, Debug, Clone)]
+pub struct WaterSpawnSettings {
+    pub transform: Transform,
+    pub grid_size: usize,
+    pub world_size: f32,
+    pub clipmap: Option<WaterClipmap>, // Infinite ocean following the camera instead of the fixed grid
+    pub tiles: Option<WaterTiles>,     // Tiled surface with per-tile LOD and culling instead of the fixed grid
+    pub gpu_displacement: bool,        // Displace the fixed grid in the vertex shader, see `WaterMaterial`
+}
+
+impl Default for WaterSpawnSettings {
+    fn default() -> Self {
+        Self {
+            transform: Transform::IDENTITY,
+            grid_size: 200,
+            world_size: 100.0,
+            clipmap: None,
+            tiles: None,
+            gpu_displacement: false,
         }
     }
 }
@@ -299,46 +1199,104 @@ pub fn spawn_water(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut water_materials: ResMut<Assets<WaterMaterial>>,
+    settings: Res<WaterSpawnSettings>,
 ) {
-    let grid_size = 200;
-    let world_size = 100.0;
-    
-    let (mesh, base_positions) = create_water_mesh(grid_size, world_size);
-    let mesh_handle = meshes.add(mesh);
-    
-    let material = materials.add(StandardMaterial {
+    let base_material = StandardMaterial {
         base_color: Color::srgb(0.0, 0.5, 0.8),
         perceptual_roughness: 0.3,
         metallic: 0.0,
         reflectance: 0.5,
         ..default()
-    });
+    };
     
-    commands.spawn((
-        Mesh3d(mesh_handle),
+    if settings.gpu_displacement {
+        let mut mesh = create_water_mesh(settings.grid_size, settings.world_size).0;
+        // Never touched on the CPU again, so it does not need to stay in the main world
+        mesh.asset_usage = RenderAssetUsages::RENDER_WORLD;
+        
+        let waves = WaterWaves::default();
+        // The CPU mesh is flat, so its bounds are padded by the largest displacement the shader can add
+        let bounds = FlatWaterBounds(mesh.compute_aabb().unwrap_or_default());
+        
+        commands.spawn((
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(water_materials.add(WaterMaterial {
+                base: base_material,
+                extension: GerstnerExtension::default(),
+            })),
+            settings.transform,
+            GlobalTransform::from(settings.transform),
+            bounds.displaced(&waves.waves),
+            bounds,
+            waves,
+        ));
+        return;
+    }
+    
+    let material = materials.add(base_material);
+    
+    if let Some(tiles) = &settings.tiles {
+        spawn_water_tiles(&mut commands, &mut meshes, material, settings.transform, tiles);
+        return;
+    }
+    
+    let water = (
         MeshMaterial3d(material),
-        Transform::from_translation(Vec3::ZERO),
+        settings.transform,
+        // Set up front so physics running before the first transform propagation sees the right pose
+        GlobalTransform::from(settings.transform),
+        WaterWaves::default(),
+    );
+    
+    if let Some(clipmap) = &settings.clipmap {
+        let (mesh, base_positions) = create_clipmap_mesh(clipmap);
+        commands.spawn((
+            water,
+            Mesh3d(meshes.add(mesh)),
+            WaterSurface {
+                grid_size: clipmap.resolution + 1,
+                world_size: clipmap.extent(),
+                vertex_count: clipmap.vertex_count(),
+                base_positions: BasePositions::new(&base_positions),
+                seams: clipmap.seam_vertices(),
+            },
+            clipmap.clone(),
+            // The vertices move with the camera, so the AABB computed at spawn would cull the ocean
+            NoFrustumCulling,
+        ));
+        return;
+    }
+    
+    let grid_size = settings.grid_size;
+    let world_size = settings.world_size;
+    let (mesh, base_positions) = create_water_mesh(grid_size, world_size);
+    
+    commands.spawn((
+        water,
+        Mesh3d(meshes.add(mesh)),
         WaterSurface {
             grid_size,
             world_size,
             vertex_count: grid_size * grid_size,
-            base_positions,
+            base_positions: BasePositions::new(&base_positions),
+            seams: Vec::new(),
         },
-        WaterWaves::default(),
     ));
 }
 
-pub fn setup_camera(mut commands: Commands) {
+pub fn setup_camera(mut commands: Commands, water: Res<WaterSpawnSettings>) {
     let isometric_angle = -26.565f32.to_radians();
     let rotation_y = 45f32.to_radians();
     let distance = 100.0;
     
     let rotation = Quat::from_euler(EulerRot::YXZ, rotation_y, isometric_angle, 0.0);
-    let translation = rotation * Vec3::new(0.0, 0.0, distance);
+    let target = water.transform.translation;
+    let translation = target + rotation * Vec3::new(0.0, 0.0, distance);
     
     commands.spawn((
         Camera3d::default(),
-        Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
+        Transform::from_translation(translation).looking_at(target, Vec3::Y),
     ));
     
     commands.spawn((
@@ -352,214 +1310,26 @@ pub fn setup_camera(mut commands: Commands) {
     ));
 }
 
-#[derive(Component, Debug)]
-pub struct FloatingBody {
-    pub buoyancy_points: Vec<Vec3>, // Relative positions from entity center to sample water height
-    pub submerged_volume: f32,
-    pub water_density: f32,
-    pub body_density: f32,
-    pub drag_coefficient: f32,
-}
-
-impl Default for FloatingBody {
-    fn default() -> Self {
-        Self {
-            // Sample points for a surfboard - corners and center
-            buoyancy_points: vec![
-                Vec3::new(-1.5, 0.0, -0.3),  // Front left
-                Vec3::new(1.5, 0.0, -0.3),   // Front right
-                Vec3::new(-1.5, 0.0, 0.3),   // Back left
-                Vec3::new(1.5, 0.0, 0.3),    // Back right
-                Vec3::new(0.0, 0.0, 0.0),    // Center
-            ],
-            submerged_volume: 0.0,
-            water_density: 1000.0,   // kg/m³
-            body_density: 200.0,     // Surfboard is much lighter than water
-            drag_coefficient: 0.1,
-        }
-    }
-}
-
-#[derive(Component, Debug)]
-pub struct Surfboard {
-    pub length: f32,
-    pub width: f32,
-    pub thickness: f32,
-}
-
-impl Default for Surfboard {
-    fn default() -> Self {
-        Self {
-            length: 3.0,   // 3 meter surfboard
-            width: 0.6,    // 60cm wide
-            thickness: 0.1, // 10cm thick
-        }
-    }
-}
-
-pub fn create_surfboard_mesh(surfboard: &Surfboard) -> Mesh {
-    let half_length = surfboard.length / 2.0;
-    let half_width = surfboard.width / 2.0;
-    let half_thickness = surfboard.thickness / 2.0;
-    
-    // Simple box mesh for the surfboard
-    let positions = vec![
-        // Bottom face
-        [-half_length, -half_thickness, -half_width],
-        [half_length, -half_thickness, -half_width],
-        [half_length, -half_thickness, half_width],
-        [-half_length, -half_thickness, half_width],
-        // Top face
-        [-half_length, half_thickness, -half_width],
-        [half_length, half_thickness, -half_width],
-        [half_length, half_thickness, half_width],
-        [-half_length, half_thickness, half_width],
-    ];
-    
-    let normals = vec![
-        // Bottom face
-        [0.0, -1.0, 0.0],
-        [0.0, -1.0, 0.0],
-        [0.0, -1.0, 0.0],
-        [0.0, -1.0, 0.0],
-        // Top face
-        [0.0, 1.0, 0.0],
-        [0.0, 1.0, 0.0],
-        [0.0, 1.0, 0.0],
-        [0.0, 1.0, 0.0],
-    ];
-    
-    let uvs = vec![
-        [0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], // Bottom
-        [0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], // Top
-    ];
-    
-    let indices = vec![
-        // Bottom face
-        0, 1, 2, 2, 3, 0,
-        // Top face
-        4, 6, 5, 6, 4, 7,
-        // Side faces
-        0, 4, 5, 5, 1, 0,
-        1, 5, 6, 6, 2, 1,
-        2, 6, 7, 7, 3, 2,
-        3, 7, 4, 4, 0, 3,
-    ];
-    
-    let mut mesh = Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
-    );
-    
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-    mesh.insert_indices(Indices::U32(indices));
-    
-    mesh
-}
-
-pub fn spawn_surfboard(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let surfboard = Surfboard::default();
-    let mesh = create_surfboard_mesh(&surfboard);
-    let mesh_handle = meshes.add(mesh);
-    
-    let material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 1.0, 0.8), // Off-white surfboard color
-        perceptual_roughness: 0.8,
-        metallic: 0.0,
-        ..default()
-    });
-    
-    commands.spawn((
-        Mesh3d(mesh_handle),
-        MeshMaterial3d(material),
-        Transform::from_translation(Vec3::new(0.0, 2.0, 0.0)), // Start above water
-        surfboard,
-        FloatingBody::default(),
-    ));
-}
-
-pub fn update_surfboard_physics(
-    time: Res<Time>,
-    water_query: Query<&WaterWaves>,
-    mut surfboard_query: Query<(&mut Transform, &mut FloatingBody, &Surfboard)>,
-) {
-    let dt = time.delta_secs();
-    let elapsed = time.elapsed_secs();
-    
-    if let Ok(waves) = water_query.single() {
-        for (mut transform, mut floating_body, _surfboard) in surfboard_query.iter_mut() {
-            let position = transform.translation;
-            
-            // Sample water height at buoyancy points
-            let mut total_buoyancy_force = 0.0;
-            let mut total_torque = Vec3::ZERO;
-            let mut submerged_points = 0;
-            
-            for buoyancy_point in &floating_body.buoyancy_points {
-                // Transform buoyancy point to world space
-                let world_point = position + transform.rotation * *buoyancy_point;
-                let sample_pos = Vec2::new(world_point.x, world_point.z);
-                
-                // Get water height at this point
-                let water_height = get_wave_height(sample_pos, &waves.waves, elapsed);
-                
-                // Calculate how much this point is submerged
-                let submersion = water_height - world_point.y;
-                
-                if submersion > 0.0 {
-                    submerged_points += 1;
-                    
-                    // Apply buoyancy force (Archimedes principle)
-                    let buoyancy_force = floating_body.water_density * 9.81 * submersion.min(0.2); // Cap submersion
-                    total_buoyancy_force += buoyancy_force;
-                    
-                    // Calculate torque for tilting
-                    let force_point = *buoyancy_point;
-                    let force_vector = Vec3::new(0.0, buoyancy_force, 0.0);
-                    total_torque += force_point.cross(force_vector);
-                }
-            }
-            
-            // Update submerged volume for reference
-            floating_body.submerged_volume = submerged_points as f32 / floating_body.buoyancy_points.len() as f32;
-            
-            // Apply forces
-            let gravity = -9.81 * floating_body.body_density;
-            let net_vertical_force = total_buoyancy_force + gravity;
-            
-            // Simple physics integration
-            let acceleration = net_vertical_force / floating_body.body_density;
-            transform.translation.y += acceleration * dt * dt;
-            
-            // Apply drag to prevent excessive bouncing
-            transform.translation.y *= 1.0 - floating_body.drag_coefficient * dt;
-            
-            // Apply gentle rotation based on wave slope (simplified)
-            if total_torque.length() > 0.01 {
-                let rotation_speed = total_torque * 0.1 * dt;
-                let rotation = Quat::from_euler(EulerRot::XYZ, rotation_speed.x, 0.0, rotation_speed.z);
-                transform.rotation = (transform.rotation * rotation).normalize();
-            }
-            
-            // Damp rotation to prevent excessive spinning
-            transform.rotation = transform.rotation.slerp(Quat::IDENTITY, floating_body.drag_coefficient * dt);
-        }
-    }
-}
-
 pub struct WaterPlugin;
 
 impl Plugin for WaterPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+        app.init_resource::<HeightQuerySettings>()
+            .init_resource::<WaterSpawnSettings>()
+            .init_resource::<WaterDisplacementSettings>()
+            .add_plugins(WaterMaterialPlugin)
+            .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
             .add_plugins(bevy::diagnostic::LogDiagnosticsPlugin::default())
             .add_systems(Startup, (spawn_water, setup_camera, spawn_surfboard))
-            .add_systems(FixedUpdate, (update_water_vertices, update_surfboard_physics));
+            .add_systems(
+                FixedUpdate,
+                (
+                    simulate_fft_ocean.before(update_water_vertices).before(update_surfboard_physics),
+                    follow_camera_with_clipmap.before(update_water_vertices),
+                    update_water_tiles.before(update_water_vertices),
+                    update_water_vertices,
+                    update_surfboard_physics,
+                ),
+            );
     }
 }
\ No newline at end of file
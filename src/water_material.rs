@@ -0,0 +1,149 @@
+use bevy::{
+    asset::{load_internal_asset, weak_handle},
+    pbr::{ExtendedMaterial, MaterialExtension},
+    prelude::*,
+    render::{
+        primitives::Aabb,
+        render_resource::{AsBindGroup, ShaderRef},
+    },
+};
+
+use crate::water::{WaterWaves, WaveParameters};
+
+pub use uniforms::{GpuWave, GpuWaves};
+
+/// Waves beyond this many are dropped from the GPU uniform
+pub const MAX_GPU_WAVES: usize = 64;
+
+const WATER_WAVES_SHADER_HANDLE: Handle<Shader> = weak_handle!("3be6382e-9b81-4af4-8c53-fe60c36fca78");
+const WATER_MATERIAL_SHADER_HANDLE: Handle<Shader> = weak_handle!("1751d09b-cced-442c-a2e7-347ec24c1c97");
+
+#[allow(dead_code, reason = "encase's `ShaderType` derive emits trait assertion fns that are never called")]
+mod uniforms {
+    use bevy::{prelude::*, render::render_resource::ShaderType};
+
+    use super::MAX_GPU_WAVES;
+
+    /// `WaveParameters` as laid out in `water_waves.wgsl`
+    #[derive(ShaderType, Debug, Clone, Copy, Default, PartialEq)]
+    pub struct GpuWave {
+        pub direction: Vec2,
+        pub amplitude: f32,
+        pub steepness: f32,
+        pub wave_number: f32,
+        pub speed: f32,
+        pub phase: f32,
+        pub _padding: f32, // Keeps the array stride at 32 bytes, a multiple of 16 as uniforms require
+    }
+
+    /// Uniform block the water vertex shader sums its Gerstner waves from
+    #[derive(ShaderType, Debug, Clone, Copy)]
+    pub struct GpuWaves {
+        pub waves: [GpuWave; MAX_GPU_WAVES],
+        pub wave_count: u32,
+        pub time: f32,
+    }
+}
+
+impl From<&WaveParameters> for GpuWave {
+    fn from(wave: &WaveParameters) -> Self {
+        Self {
+            direction: wave.direction,
+            amplitude: wave.amplitude,
+            steepness: wave.steepness,
+            wave_number: wave.wave_number,
+            speed: wave.speed,
+            phase: wave.phase,
+            _padding: 0.0,
+        }
+    }
+}
+
+impl GpuWaves {
+    pub fn new(waves: &[WaveParameters], time: f32) -> Self {
+        let mut gpu_waves = [GpuWave::default(); MAX_GPU_WAVES];
+        for (gpu_wave, wave) in gpu_waves.iter_mut().zip(waves) {
+            *gpu_wave = wave.into();
+        }
+
+        Self {
+            waves: gpu_waves,
+            wave_count: waves.len().min(MAX_GPU_WAVES) as u32,
+            time,
+        }
+    }
+}
+
+impl Default for GpuWaves {
+    fn default() -> Self {
+        Self::new(&[], 0.0)
+    }
+}
+
+/// Material extension that displaces the water mesh in the vertex shader instead of on the CPU
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone, Default)]
+pub struct GerstnerExtension {
+    // Bindings 0-99 belong to the StandardMaterial being extended
+    #[uniform(100)]
+    pub waves: GpuWaves,
+}
+
+impl MaterialExtension for GerstnerExtension {
+    fn vertex_shader() -> ShaderRef {
+        WATER_MATERIAL_SHADER_HANDLE.into()
+    }
+}
+
+/// Water rendered with GPU displacement. Meshes using it stay flat on the CPU; physics keeps sampling
+/// `WaterWaves` through `WaterQuery`, which evaluates the same sum.
+pub type WaterMaterial = ExtendedMaterial<StandardMaterial, GerstnerExtension>;
+
+/// Bounds of a GPU-displaced mesh before displacement, which `update_water_material` pads into its `Aabb`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FlatWaterBounds(pub Aabb);
+
+impl FlatWaterBounds {
+    /// The flat bounds grown by the furthest any point can be displaced by the waves
+    pub fn displaced(&self, waves: &[WaveParameters]) -> Aabb {
+        let reach: f32 = waves.iter().map(|wave| wave.amplitude * (1.0 + wave.steepness)).sum();
+        Aabb {
+            center: self.0.center,
+            half_extents: self.0.half_extents + reach,
+        }
+    }
+}
+
+/// Copy every water entity's waves and the current time into its `WaterMaterial`, and refit its culling bounds
+/// when the waves change. Runs in `FixedUpdate` so the shader sees the same time as the CPU-side physics.
+pub fn update_water_material(
+    time: Res<Time>,
+    mut materials: ResMut<Assets<WaterMaterial>>,
+    mut query: Query<(&MeshMaterial3d<WaterMaterial>, Ref<WaterWaves>, Option<(&FlatWaterBounds, &mut Aabb)>)>,
+) {
+    for (material, waves, bounds) in query.iter_mut() {
+        if let Some((flat, mut aabb)) = bounds.filter(|_| waves.is_changed()) {
+            *aabb = flat.displaced(&waves.waves);
+        }
+
+        if let Some(material) = materials.get_mut(&material.0) {
+            if waves.waves.len() > MAX_GPU_WAVES {
+                let count = waves.waves.len();
+                warn_once!("Only the first {MAX_GPU_WAVES} of {count} waves are displaced on the GPU");
+            }
+            material.extension.waves = GpuWaves::new(&waves.waves, time.elapsed_secs());
+        }
+    }
+}
+
+/// Registers `WaterMaterial` and its shaders
+pub struct WaterMaterialPlugin;
+
+impl Plugin for WaterMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, WATER_WAVES_SHADER_HANDLE, "shaders/water_waves.wgsl", Shader::from_wgsl);
+        load_internal_asset!(app, WATER_MATERIAL_SHADER_HANDLE, "shaders/water_material.wgsl", Shader::from_wgsl);
+
+        app.add_plugins(MaterialPlugin::<WaterMaterial>::default())
+            .add_systems(FixedUpdate, update_water_material);
+    }
+}
@@ -0,0 +1,440 @@
+use std::{
+    cell::{Ref, RefCell},
+    cmp::Reverse,
+    ops::{Add, Mul},
+};
+
+use bevy::{
+    ecs::{
+        component::Tick,
+        system::{SystemChangeTick, SystemParam},
+    },
+    prelude::*,
+};
+
+use crate::{
+    batch_query::get_displaced_wave_heights,
+    fft_ocean::FftOcean,
+    water::{
+        HeightQuerySettings, WaterBounds, WaterSample, WaterWaves, WaveParameters, sample_water,
+        sample_water_velocity_at_depth,
+    },
+};
+
+/// One water entity as seen by `WaterQuery`
+#[derive(Clone, Copy)]
+pub struct WaterBody<'a> {
+    pub transform: &'a GlobalTransform,
+    pub waves: &'a [WaveParameters],
+    pub fft_ocean: Option<&'a FftOcean>,
+    pub bounds: Option<&'a WaterBounds>,
+}
+
+impl WaterBody<'_> {
+    /// Unbounded bodies sit below every bounded one
+    pub fn priority(&self) -> i32 {
+        self.bounds.map_or(0, |bounds| bounds.priority)
+    }
+
+    /// How much of this body covers the world-space `position`, from 0.0 to 1.0
+    pub fn coverage(&self, position: Vec3) -> f32 {
+        let Some(bounds) = self.bounds else {
+            return 1.0;
+        };
+        let local = self.transform.affine().inverse().transform_point3(position);
+        bounds.coverage(Vec2::new(local.x, local.z))
+    }
+
+    /// Full surface sample under the world-space `position`, with every vector in world space
+    pub fn sample(&self, position: Vec3, time: f32, settings: &HeightQuerySettings) -> WaterSample {
+        let affine = self.transform.affine();
+        let local = affine.inverse().transform_point3(position);
+        let local_xz = Vec2::new(local.x, local.z);
+
+        let mut sample = sample_water(local_xz, self.waves, time, settings);
+        if let Some(ocean) = self.fft_ocean {
+            // The FFT sea rides on top of the Gerstner waves, slopes and orbital motion adding up
+            sample.position.y += ocean.height_at(local_xz);
+            let gradient = gradient_from_normal(sample.normal) + ocean.gradient_at(local_xz);
+            sample.normal = Vec3::new(-gradient.x, 1.0, -gradient.y).normalize();
+            sample.velocity += ocean.velocity_at(local_xz);
+            sample.acceleration += ocean.acceleration_at(local_xz);
+        }
+
+        // Normals transform with the inverse transpose so non-uniform scale keeps them perpendicular
+        let normal = (affine.matrix3.inverse().transpose() * Vec3A::from(sample.normal)).normalize_or(Vec3A::Y);
+        let normal = Vec3::from(normal);
+        WaterSample {
+            position: affine.transform_point3(sample.position),
+            normal,
+            gradient: gradient_from_normal(normal),
+            velocity: affine.transform_vector3(sample.velocity),
+            acceleration: affine.transform_vector3(sample.acceleration),
+        }
+    }
+
+    /// World-space height of the undisturbed water plane under `position`
+    pub fn still_water_level(&self, position: Vec3) -> f32 {
+        let affine = self.transform.affine();
+        let local = affine.inverse().transform_point3(position);
+        affine.transform_point3(Vec3::new(local.x, 0.0, local.z)).y
+    }
+
+    /// Orbital velocity of the water at `position`, decaying with depth below the still water level
+    pub fn velocity_at_depth(&self, position: Vec3, time: f32, settings: &HeightQuerySettings) -> Vec3 {
+        let affine = self.transform.affine();
+        let local = affine.inverse().transform_point3(position);
+        affine.transform_vector3(self.local_velocity_at_depth(local, time, settings))
+    }
+
+    /// `velocity_at_depth` in the body's local space. An FFT sea decays as a whole at its peak wave number,
+    /// where its Gerstner waves decay each at their own.
+    fn local_velocity_at_depth(&self, local: Vec3, time: f32, settings: &HeightQuerySettings) -> Vec3 {
+        let local_xz = Vec2::new(local.x, local.z);
+        let depth = -local.y;
+        let mut velocity = sample_water_velocity_at_depth(local_xz, depth, self.waves, time, settings);
+        if let Some(ocean) = self.fft_ocean {
+            let decay = (-ocean.peak_wave_number() * depth.max(0.0)).exp();
+            velocity += ocean.velocity_at(local_xz) * decay;
+        }
+        velocity
+    }
+}
+
+/// Scratch buffers so batched queries do not allocate every tick
+#[derive(Default)]
+pub struct WaterQueryScratch {
+    positions_x: Vec<f32>,
+    positions_z: Vec<f32>,
+    body_heights: Vec<f32>,
+    uncovered: Vec<f32>,
+    total_weights: Vec<f32>,
+}
+
+/// Water entities highest priority first, sorted once per system run
+#[derive(Default)]
+pub struct PriorityOrder {
+    sorted_at: Option<Tick>,
+    entities: Vec<Entity>,
+}
+
+/// Samples the water surface in world space without callers needing to know about `WaterWaves`,
+/// `FftOcean`, the water entities' transforms and bounds or the current time.
+///
+/// Where bounded bodies overlap, higher priority bodies cover lower ones and blend into them over
+/// their `blend_distance`. Outside every body there is no water and the queries return `None`.
+#[derive(SystemParam)]
+pub struct WaterQuery<'w, 's> {
+    time: Res<'w, Time>,
+    settings: Res<'w, HeightQuerySettings>,
+    bodies: Query<'w, 's, WaterBodyData>,
+    ticks: SystemChangeTick,
+    order: Local<'s, RefCell<PriorityOrder>>,
+    scratch: Local<'s, WaterQueryScratch>,
+}
+
+impl WaterQuery<'_, '_> {
+    /// Simulation time the water is evaluated at, in seconds
+    pub fn elapsed_secs(&self) -> f32 {
+        self.time.elapsed_secs()
+    }
+
+    /// Every water body in the world
+    pub fn bodies(&self) -> impl Iterator<Item = WaterBody<'_>> {
+        self.bodies.iter().map(water_body)
+    }
+
+    /// Bodies covering `position` with blend weights summing to 1.0, highest priority first.
+    /// Empty where there is no water.
+    pub fn bodies_at(&self, position: Vec3) -> Vec<(WaterBody<'_>, f32)> {
+        let mut covering = Vec::new();
+        let total = self.each_covering(position, |body, weight| covering.push((body, weight)));
+        for (_, weight) in &mut covering {
+            *weight /= total;
+        }
+        covering
+    }
+
+    /// The body contributing most to the water at `position`
+    pub fn body_at(&self, position: Vec3) -> Option<WaterBody<'_>> {
+        let mut most: Option<(WaterBody, f32)> = None;
+        self.each_covering(position, |body, weight| {
+            if most.is_none_or(|(_, most)| weight > most) {
+                most = Some((body, weight));
+            }
+        });
+        most.map(|(body, _)| body)
+    }
+
+    /// World-space height of the water surface under `position`
+    pub fn height(&self, position: Vec3) -> Option<f32> {
+        self.sample(position).map(|sample| sample.position.y)
+    }
+
+    /// World-space surface normal under `position`
+    pub fn normal(&self, position: Vec3) -> Option<Vec3> {
+        self.sample(position).map(|sample| sample.normal)
+    }
+
+    /// Orbital velocity of the surface water under `position`
+    pub fn velocity(&self, position: Vec3) -> Option<Vec3> {
+        self.sample(position).map(|sample| sample.velocity)
+    }
+
+    /// World-space height of the undisturbed water plane under `position`
+    pub fn still_water_level(&self, position: Vec3) -> Option<f32> {
+        self.blend(position, |body| body.still_water_level(position))
+    }
+
+    /// Full surface sample under `position`, blended between overlapping bodies
+    pub fn sample(&self, position: Vec3) -> Option<WaterSample> {
+        let time = self.time.elapsed_secs();
+        let mut blended = WaterSample {
+            position: Vec3::ZERO,
+            normal: Vec3::ZERO,
+            gradient: Vec2::ZERO,
+            velocity: Vec3::ZERO,
+            acceleration: Vec3::ZERO,
+        };
+        let total = self.each_covering(position, |body, weight| {
+            let sample = body.sample(position, time, &self.settings);
+            blended.position += sample.position * weight;
+            blended.normal += sample.normal * weight;
+            blended.velocity += sample.velocity * weight;
+            blended.acceleration += sample.acceleration * weight;
+        });
+        if total <= 0.0 {
+            return None;
+        }
+
+        blended.position /= total;
+        blended.velocity /= total;
+        blended.acceleration /= total;
+        blended.normal = blended.normal.normalize_or(Vec3::Y);
+        blended.gradient = gradient_from_normal(blended.normal);
+
+        Some(blended)
+    }
+
+    /// Orbital velocity of the water at `position`, decaying with depth below the still water level.
+    /// Points above the water get the surface velocity.
+    pub fn velocity_at_depth(&self, position: Vec3) -> Option<Vec3> {
+        let time = self.time.elapsed_secs();
+        self.blend(position, |body| body.velocity_at_depth(position, time, &self.settings))
+    }
+
+    /// World-space surface heights under many points at once, using the SIMD batch path.
+    /// Points outside every water body get `f32::NEG_INFINITY`. Returns false, leaving `heights`
+    /// untouched, if the world has no water at all.
+    pub fn heights(&mut self, positions: &[Vec3], heights: &mut [f32]) -> bool {
+        assert_eq!(positions.len(), heights.len());
+        let Self {
+            time,
+            settings,
+            bodies,
+            ticks,
+            order,
+            scratch,
+        } = self;
+        let order = order.get_mut();
+        order.refresh(ticks.this_run(), bodies);
+        if order.entities.is_empty() {
+            return false;
+        }
+        let scratch = &mut **scratch;
+
+        let count = positions.len();
+        heights.fill(0.0);
+        scratch.uncovered.clear();
+        scratch.uncovered.resize(count, 1.0);
+        scratch.total_weights.clear();
+        scratch.total_weights.resize(count, 0.0);
+        scratch.body_heights.resize(count, 0.0);
+
+        for &entity in &order.entities {
+            let body = water_body(bodies.get(entity).unwrap());
+            let affine = body.transform.affine();
+            let world_to_local = affine.inverse();
+            scratch.positions_x.clear();
+            scratch.positions_z.clear();
+            for position in positions {
+                let local = world_to_local.transform_point3(*position);
+                scratch.positions_x.push(local.x);
+                scratch.positions_z.push(local.z);
+            }
+
+            get_displaced_wave_heights(
+                &scratch.positions_x,
+                &scratch.positions_z,
+                body.waves,
+                time.elapsed_secs(),
+                settings,
+                &mut scratch.body_heights,
+            );
+
+            for (i, height_sum) in heights.iter_mut().enumerate() {
+                let local_xz = Vec2::new(scratch.positions_x[i], scratch.positions_z[i]);
+                let coverage = body.bounds.map_or(1.0, |bounds| bounds.coverage(local_xz));
+                if coverage <= 0.0 {
+                    continue;
+                }
+
+                let local_height =
+                    scratch.body_heights[i] + body.fft_ocean.map_or(0.0, |ocean| ocean.height_at(local_xz));
+                let height = affine.transform_point3(Vec3::new(local_xz.x, local_height, local_xz.y)).y;
+
+                let weight = coverage * scratch.uncovered[i];
+                *height_sum += height * weight;
+                scratch.total_weights[i] += weight;
+                scratch.uncovered[i] *= 1.0 - coverage;
+            }
+        }
+
+        for (height, total_weight) in heights.iter_mut().zip(&scratch.total_weights) {
+            *height = if *total_weight > 0.0 {
+                *height / total_weight
+            } else {
+                f32::NEG_INFINITY
+            };
+        }
+
+        true
+    }
+
+    /// `velocity_at_depth` for many points at once, inverting each water body's transform only once.
+    /// Points outside every water body get `Vec3::ZERO`. Returns false, leaving `velocities` untouched,
+    /// if the world has no water at all.
+    pub fn velocities_at_depth(&mut self, positions: &[Vec3], velocities: &mut [Vec3]) -> bool {
+        assert_eq!(positions.len(), velocities.len());
+        let Self {
+            time,
+            settings,
+            bodies,
+            ticks,
+            order,
+            scratch,
+        } = self;
+        let order = order.get_mut();
+        order.refresh(ticks.this_run(), bodies);
+        if order.entities.is_empty() {
+            return false;
+        }
+        let scratch = &mut **scratch;
+        let time = time.elapsed_secs();
+
+        let count = positions.len();
+        velocities.fill(Vec3::ZERO);
+        scratch.uncovered.clear();
+        scratch.uncovered.resize(count, 1.0);
+        scratch.total_weights.clear();
+        scratch.total_weights.resize(count, 0.0);
+
+        for &entity in &order.entities {
+            let body = water_body(bodies.get(entity).unwrap());
+            let affine = body.transform.affine();
+            let world_to_local = affine.inverse();
+
+            for (i, (position, velocity_sum)) in positions.iter().zip(velocities.iter_mut()).enumerate() {
+                if scratch.uncovered[i] <= 0.0 {
+                    continue;
+                }
+                let local = world_to_local.transform_point3(*position);
+                let coverage = body.bounds.map_or(1.0, |bounds| bounds.coverage(Vec2::new(local.x, local.z)));
+                if coverage <= 0.0 {
+                    continue;
+                }
+
+                let velocity = body.local_velocity_at_depth(local, time, settings);
+                let weight = coverage * scratch.uncovered[i];
+                *velocity_sum += affine.transform_vector3(velocity) * weight;
+                scratch.total_weights[i] += weight;
+                scratch.uncovered[i] *= 1.0 - coverage;
+            }
+        }
+
+        for (velocity, &total_weight) in velocities.iter_mut().zip(&scratch.total_weights) {
+            if total_weight > 0.0 {
+                *velocity /= total_weight;
+            }
+        }
+
+        true
+    }
+
+    /// Weighted average of `value` over the bodies covering `position`
+    fn blend<T>(&self, position: Vec3, value: impl Fn(&WaterBody) -> T) -> Option<T>
+    where
+        T: Copy + Default + Add<Output = T> + Mul<f32, Output = T>,
+    {
+        let mut blended = T::default();
+        let total = self.each_covering(position, |body, weight| {
+            blended = blended + value(&body) * weight;
+        });
+        (total > 0.0).then(|| blended * (1.0 / total))
+    }
+
+    /// Visits the bodies covering `position` highest priority first, with weights that still need
+    /// dividing by the returned total. The total is 0.0 where there is no water.
+    fn each_covering<'a>(&'a self, position: Vec3, mut visit: impl FnMut(WaterBody<'a>, f32)) -> f32 {
+        let mut uncovered = 1.0;
+        for &entity in self.priority_order().iter() {
+            let body = water_body(self.bodies.get(entity).unwrap());
+            let coverage = body.coverage(position);
+            if coverage > 0.0 {
+                visit(body, coverage * uncovered);
+                uncovered *= 1.0 - coverage;
+            }
+            if uncovered <= 0.0 {
+                break;
+            }
+        }
+        1.0 - uncovered
+    }
+
+    /// Water entities highest priority first
+    fn priority_order(&self) -> Ref<'_, [Entity]> {
+        self.order.borrow_mut().refresh(self.ticks.this_run(), &self.bodies);
+        Ref::map(self.order.borrow(), |order| order.entities.as_slice())
+    }
+}
+
+impl PriorityOrder {
+    /// Re-sorts the bodies at most once per system run, keeping query order between equal priorities
+    fn refresh(&mut self, this_run: Tick, bodies: &Query<WaterBodyData>) {
+        if self.sorted_at == Some(this_run) {
+            return;
+        }
+        self.sorted_at = Some(this_run);
+        self.entities.clear();
+        self.entities.extend(bodies.iter().map(|(entity, ..)| entity));
+        self.entities.sort_by_key(|&entity| Reverse(water_body(bodies.get(entity).unwrap()).priority()));
+    }
+}
+
+/// Components making up one water entity
+type WaterBodyData = (
+    Entity,
+    &'static GlobalTransform,
+    AnyOf<(&'static WaterWaves, &'static FftOcean)>,
+    Option<&'static WaterBounds>,
+);
+
+fn water_body<'a>(
+    (_, transform, (waves, fft_ocean), bounds): (
+        Entity,
+        &'a GlobalTransform,
+        (Option<&'a WaterWaves>, Option<&'a FftOcean>),
+        Option<&'a WaterBounds>,
+    ),
+) -> WaterBody<'a> {
+    WaterBody {
+        transform,
+        waves: waves.map_or(&[], |waves| &waves.waves),
+        fft_ocean,
+        bounds,
+    }
+}
+
+fn gradient_from_normal(normal: Vec3) -> Vec2 {
+    Vec2::new(-normal.x, -normal.z) / normal.y.max(1e-6)
+}
@@ -0,0 +1,309 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        primitives::{Aabb, Frustum},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+use crate::water::{BasePositions, WaterSurface, WaterWaves, WaveParameters};
+
+/// Neighbour directions of a tile (-x, +x, -z, +z), in the order `WaterTile::neighbour_lods` stores them
+const NEIGHBOURS: [IVec2; 4] = [IVec2::NEG_X, IVec2::X, IVec2::NEG_Y, IVec2::Y];
+
+/// Water surface split into a square field of tiles, each with its own mesh.
+///
+/// Tiles pick their grid density from the distance to the active camera and tiles outside the camera
+/// frustum are not displaced. Neighbouring tiles differ by at most one LOD, and the finer tile stitches
+/// its border onto the coarser one so there are no cracks.
+#[derive(Component, Debug, Clone)]
+pub struct WaterTiles {
+    pub tile_count: usize,     // Tiles per side
+    pub tile_size: f32,        // Width of one tile in meters
+    pub max_resolution: usize, // Grid cells per side of the densest tiles, must be a power of two
+    pub lod_count: usize,      // Number of LODs, each halving the resolution of the previous one
+    pub lod_distance: f32,     // Tiles closer than this use the full resolution, every doubling drops a LOD
+    pub cull_margin: f32,      // Extra bounds padding in meters, e.g. for an `FftOcean` on the same water
+}
+
+impl Default for WaterTiles {
+    fn default() -> Self {
+        Self {
+            tile_count: 8,
+            tile_size: 25.0,
+            max_resolution: 64,
+            lod_count: 4,
+            lod_distance: 40.0,
+            cull_margin: 1.0,
+        }
+    }
+}
+
+impl WaterTiles {
+    /// Grid cells per side of a tile at `lod`
+    pub fn resolution(&self, lod: usize) -> usize {
+        (self.max_resolution >> lod).max(1)
+    }
+
+    /// All tile coordinates in row-major order, matching `select_lods`
+    pub fn coords(&self) -> impl Iterator<Item = IVec2> + '_ {
+        let count = self.tile_count as i32;
+        (0..count).flat_map(move |z| (0..count).map(move |x| IVec2::new(x, z)))
+    }
+
+    /// Water-local XZ corners of the tile at `coord`, with the whole field centred on the origin
+    pub fn tile_rect(&self, coord: IVec2) -> Rect {
+        let half = self.tile_count as f32 * self.tile_size / 2.0;
+        let min = coord.as_vec2() * self.tile_size - half;
+        Rect::from_corners(min, min + self.tile_size)
+    }
+
+    /// LOD of every tile seen from the water-local `focus`, limited so that neighbours differ by at most one
+    pub fn select_lods(&self, focus: Vec3) -> Vec<usize> {
+        let mut lods: Vec<usize> = self
+            .coords()
+            .map(|coord| {
+                let rect = self.tile_rect(coord);
+                let closest = focus.xz().clamp(rect.min, rect.max);
+                let distance = focus.distance(Vec3::new(closest.x, 0.0, closest.y));
+                let lod = (distance / self.lod_distance).max(1.0).log2().floor() as usize;
+                lod.min(self.lod_count - 1)
+            })
+            .collect();
+
+        // Pull coarse tiles towards their finer neighbours until the whole field is balanced
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for coord in self.coords() {
+                let index = self.tile_index(coord);
+                for neighbour in NEIGHBOURS.iter().filter_map(|offset| self.neighbour_index(coord + *offset)) {
+                    if lods[index] > lods[neighbour] + 1 {
+                        lods[index] = lods[neighbour] + 1;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        lods
+    }
+
+    /// LODs of the four neighbours of `coord`, `None` at the edge of the field
+    pub fn neighbour_lods(&self, coord: IVec2, lods: &[usize]) -> [Option<usize>; 4] {
+        NEIGHBOURS.map(|offset| self.neighbour_index(coord + offset).map(|index| lods[index]))
+    }
+
+    /// Vertices on borders shared with a coarser neighbour that fall between two of the neighbour's
+    /// vertices, as `[vertex, edge start, edge end]`. See `stitch_seams`.
+    pub fn seam_vertices(&self, lod: usize, neighbour_lods: [Option<usize>; 4]) -> Vec<[u32; 3]> {
+        let resolution = self.resolution(lod);
+        let grid = resolution + 1;
+        let index = |x: usize, z: usize| (z * grid + x) as u32;
+        let mut seams = Vec::new();
+
+        for (side, neighbour_lod) in neighbour_lods.into_iter().enumerate() {
+            if neighbour_lod.is_none_or(|neighbour_lod| neighbour_lod <= lod) {
+                continue;
+            }
+            for i in (1..resolution).step_by(2) {
+                seams.push(match side {
+                    0 => [index(0, i), index(0, i - 1), index(0, i + 1)],
+                    1 => [index(resolution, i), index(resolution, i - 1), index(resolution, i + 1)],
+                    2 => [index(i, 0), index(i - 1, 0), index(i + 1, 0)],
+                    _ => [index(i, resolution), index(i - 1, resolution), index(i + 1, resolution)],
+                });
+            }
+        }
+
+        seams
+    }
+
+    /// Bounds of the displaced tile at `coord` in water-local space
+    pub fn tile_aabb(&self, coord: IVec2, waves: &[WaveParameters]) -> Aabb {
+        let vertical: f32 = waves.iter().map(|wave| wave.amplitude).sum::<f32>() + self.cull_margin;
+        let horizontal: f32 =
+            waves.iter().map(|wave| wave.steepness * wave.amplitude).sum::<f32>() + self.cull_margin;
+        let rect = self.tile_rect(coord);
+        Aabb::from_min_max(
+            Vec3::new(rect.min.x - horizontal, -vertical, rect.min.y - horizontal),
+            Vec3::new(rect.max.x + horizontal, vertical, rect.max.y + horizontal),
+        )
+    }
+
+    fn tile_index(&self, coord: IVec2) -> usize {
+        coord.y as usize * self.tile_count + coord.x as usize
+    }
+
+    fn neighbour_index(&self, coord: IVec2) -> Option<usize> {
+        let count = self.tile_count as i32;
+        (coord.cmpge(IVec2::ZERO).all() && coord.cmplt(IVec2::splat(count)).all()).then(|| self.tile_index(coord))
+    }
+}
+
+/// One tile of a `WaterTiles` field, spawned as a child of the water entity
+#[derive(Component, Debug, Clone)]
+pub struct WaterTile {
+    pub coord: IVec2,
+    pub lod: usize,
+    pub neighbour_lods: [Option<usize>; 4],
+    pub visible: bool, // Inside the active camera's frustum, `update_water_vertices` skips the tile otherwise
+}
+
+/// Build the mesh of the tile at `coord` with the grid density of `lod`, along with its base positions
+pub fn create_tile_mesh(tiles: &WaterTiles, coord: IVec2, lod: usize) -> (Mesh, Vec<Vec3>) {
+    let resolution = tiles.resolution(lod);
+    let grid = resolution + 1;
+    let mut positions = Vec::with_capacity(grid * grid);
+    let mut normals = Vec::with_capacity(grid * grid);
+    let mut uvs = Vec::with_capacity(grid * grid);
+    let mut base_positions = Vec::with_capacity(grid * grid);
+
+    // Positions come from integer offsets in finest-grid cells, so tiles at different LODs
+    // produce bit-identical positions along their shared border
+    let unit = tiles.tile_size / tiles.max_resolution as f32;
+    let stride = (tiles.max_resolution / resolution) as i32;
+    let half_field = (tiles.tile_count * tiles.max_resolution / 2) as i32;
+    let origin = coord * tiles.max_resolution as i32 - IVec2::splat(half_field);
+
+    for z in 0..grid {
+        for x in 0..grid {
+            let cell = origin + IVec2::new(x as i32, z as i32) * stride;
+            let pos = Vec3::new(cell.x as f32 * unit, 0.0, cell.y as f32 * unit);
+
+            positions.push([pos.x, pos.y, pos.z]);
+            base_positions.push(pos);
+            normals.push([0.0, 1.0, 0.0]);
+            uvs.push([x as f32 / resolution as f32, z as f32 / resolution as f32]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(resolution * resolution * 6);
+    for z in 0..resolution {
+        for x in 0..resolution {
+            let idx = (z * grid + x) as u32;
+            let grid = grid as u32;
+            indices.extend_from_slice(&[idx, idx + grid, idx + 1, idx + 1, idx + grid, idx + grid + 1]);
+        }
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+
+    (mesh, base_positions)
+}
+
+/// Spawn a tiled water surface at `transform`. The returned water entity holds the `WaterWaves`
+/// and every tile is one of its children.
+pub fn spawn_water_tiles(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    transform: Transform,
+    tiles: &WaterTiles,
+) -> Entity {
+    let waves = WaterWaves::default();
+    let tile_entities: Vec<Entity> = tiles
+        .coords()
+        .map(|coord| {
+            let (mesh, base_positions) = create_tile_mesh(tiles, coord, 0);
+            let resolution = tiles.resolution(0);
+            commands
+                .spawn((
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(material.clone()),
+                    Transform::IDENTITY,
+                    GlobalTransform::from(transform),
+                    WaterSurface {
+                        grid_size: resolution + 1,
+                        world_size: tiles.tile_size,
+                        vertex_count: base_positions.len(),
+                        base_positions: BasePositions::new(&base_positions),
+                        seams: Vec::new(),
+                    },
+                    WaterTile {
+                        coord,
+                        lod: 0,
+                        neighbour_lods: tiles.neighbour_lods(coord, &vec![0; tiles.tile_count * tiles.tile_count]),
+                        visible: true,
+                    },
+                    tiles.tile_aabb(coord, &waves.waves),
+                ))
+                .id()
+        })
+        .collect();
+
+    commands
+        .spawn((
+            transform,
+            // Set up front so physics running before the first transform propagation sees the right pose
+            GlobalTransform::from(transform),
+            Visibility::default(),
+            waves,
+            tiles.clone(),
+        ))
+        .add_children(&tile_entities)
+        .id()
+}
+
+/// Pick the LOD of every tile from the active camera, rebuilding tile meshes whose LOD or neighbours
+/// changed, and flag the tiles inside the camera frustum
+pub fn update_water_tiles(
+    cameras: Query<(&Camera, &GlobalTransform, &Frustum)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    fields: Query<(&WaterTiles, &GlobalTransform, Option<&WaterWaves>, &Children)>,
+    mut tiles: Query<(&mut WaterTile, &mut WaterSurface, &mut Aabb, &Mesh3d)>,
+) {
+    let Some((_, camera_transform, frustum)) = cameras.iter().find(|(camera, _, _)| camera.is_active) else {
+        return;
+    };
+
+    for (field, transform, waves, children) in fields.iter() {
+        let waves: &[WaveParameters] = waves.map_or(&[], |waves| &waves.waves);
+        let world_from_local = transform.affine();
+        let focus = world_from_local.inverse().transform_point3(camera_transform.translation());
+        let lods = field.select_lods(focus);
+
+        let mut tile_iter = tiles.iter_many_mut(children);
+        while let Some((mut tile, mut surface, mut aabb, mesh_3d)) = tile_iter.fetch_next() {
+            let coord = tile.coord;
+            let lod = lods[field.tile_index(coord)];
+            let neighbour_lods = field.neighbour_lods(coord, &lods);
+
+            if tile.lod != lod || tile.neighbour_lods != neighbour_lods {
+                // Without its mesh the tile stays at its old LOD, and tries again next run
+                let rebuilt = tile.lod == lod
+                    || meshes.get_mut(&mesh_3d.0).is_some_and(|mesh| {
+                        let (new_mesh, base_positions) = create_tile_mesh(field, coord, lod);
+                        *mesh = new_mesh;
+                        surface.grid_size = field.resolution(lod) + 1;
+                        surface.vertex_count = base_positions.len();
+                        surface.base_positions.set(&base_positions);
+                        true
+                    });
+                if rebuilt {
+                    surface.seams = field.seam_vertices(lod, neighbour_lods);
+                    tile.lod = lod;
+                    tile.neighbour_lods = neighbour_lods;
+                }
+            }
+
+            let bounds = field.tile_aabb(coord, waves);
+            if *aabb != bounds {
+                *aabb = bounds;
+            }
+            let visible = frustum.intersects_obb(&aabb, &world_from_local, true, true);
+            if tile.visible != visible {
+                tile.visible = visible;
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use isosurf::{
+    simd::{LaneWidth, MAX_LANES},
+    water::{BasePositions, WaterWaves, displace_water_surface_with_lanes},
+};
+
+fn positions(count: usize) -> Vec<Vec3> {
+    (0..count)
+        .map(|i| Vec3::new(i as f32 * 2.3 - 20.0, 0.0, (i as f32 * 0.7).cos() * 15.0))
+        .collect()
+}
+
+#[test]
+fn lanes_are_padded_with_the_last_vertex() {
+    let input = positions(5);
+    let mut base_positions = BasePositions::new(&input);
+
+    assert_eq!(base_positions.len(), 5);
+    assert_eq!(base_positions.padded_len(), MAX_LANES);
+    assert_eq!(base_positions.x().len(), MAX_LANES);
+    assert!(base_positions.x()[5..].iter().all(|x| *x == input[4].x));
+    assert!(base_positions.z()[5..].iter().all(|z| *z == input[4].z));
+    assert_eq!(base_positions.iter().collect::<Vec<_>>(), input);
+
+    let waves = WaterWaves::default();
+    base_positions.cache_wave_dots(&waves.waves);
+    assert!(base_positions.wave_dots(&waves.waves).is_some());
+
+    let input = positions(MAX_LANES + 3);
+    base_positions.set(&input);
+    assert_eq!(base_positions.padded_len(), MAX_LANES * 2);
+    assert_eq!(base_positions.get(MAX_LANES + 2), input[MAX_LANES + 2]);
+    assert!(base_positions.wave_dots(&waves.waves).is_none(), "new positions invalidate the cache");
+}
+
+#[test]
+fn cached_wave_dots_displace_the_same_surface() {
+    let mut waves = WaterWaves::default();
+    let time = 4.2;
+    let count = 45;
+    let mut base_positions = BasePositions::new(&positions(count));
+
+    let displace = |base_positions: &BasePositions, waves: &WaterWaves, lane_width| {
+        let mut surface = (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count]);
+        displace_water_surface_with_lanes(
+            lane_width,
+            base_positions,
+            &waves.waves,
+            time,
+            &mut surface.0,
+            &mut surface.1,
+            &mut surface.2,
+        );
+        surface
+    };
+
+    for lane_width in LaneWidth::ALL {
+        let computed = displace(&base_positions, &waves, lane_width);
+        base_positions.cache_wave_dots(&waves.waves);
+        let cached = displace(&base_positions, &waves, lane_width);
+        // Bit-for-bit identical, not just close
+        assert_eq!(cached, computed, "{lane_width:?}");
+
+        // A turned wave no longer matches the cache, which then gets ignored
+        waves.waves[0].direction = Vec2::from_angle(0.3).rotate(waves.waves[0].direction);
+        assert!(base_positions.wave_dots(&waves.waves).is_none());
+        let uncached = BasePositions::new(&positions(count));
+        assert_eq!(displace(&base_positions, &waves, lane_width), displace(&uncached, &waves, lane_width));
+    }
+}
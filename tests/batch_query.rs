@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use isosurf::{
+    batch_query::{WaterSampleBatch, get_displaced_wave_heights, sample_water_batch, sample_water_batch_with_lanes},
+    simd::LaneWidth,
+    water::{HeightQuerySettings, WaterWaves, get_displaced_wave_height, sample_water},
+};
+
+/// Scattered points, deliberately not a multiple of the lane width
+fn sample_points() -> (Vec<f32>, Vec<f32>) {
+    (0..37)
+        .map(|i| {
+            let i = i as f32;
+            ((i * 7.31).sin() * 40.0, (i * 3.17).cos() * 40.0)
+        })
+        .unzip()
+}
+
+#[test]
+fn batched_heights_match_scalar_query() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    let (xs, zs) = sample_points();
+    let mut heights = vec![0.0; xs.len()];
+
+    get_displaced_wave_heights(&xs, &zs, &waves.waves, 6.5, &settings, &mut heights);
+
+    for ((&x, &z), &height) in xs.iter().zip(&zs).zip(&heights) {
+        let expected = get_displaced_wave_height(Vec2::new(x, z), &waves.waves, 6.5, &settings);
+        assert!((height - expected).abs() < 1e-3, "{height} != {expected} at ({x}, {z})");
+    }
+}
+
+#[test]
+fn batched_samples_match_scalar_query() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    let (xs, zs) = sample_points();
+    let mut batch = WaterSampleBatch::default();
+
+    sample_water_batch(&xs, &zs, &waves.waves, 2.25, &settings, &mut batch);
+    let detected = batch.clone();
+
+    for lane_width in LaneWidth::ALL {
+        sample_water_batch_with_lanes(lane_width, &xs, &zs, &waves.waves, 2.25, &settings, &mut batch);
+        assert_eq!(batch.heights.len(), xs.len());
+        for (i, (&x, &z)) in xs.iter().zip(&zs).enumerate() {
+            let expected = sample_water(Vec2::new(x, z), &waves.waves, 2.25, &settings);
+            assert!((batch.heights[i] - expected.position.y).abs() < 1e-3);
+            let normal = batch.normals[i];
+            assert!(normal.distance(expected.normal) < 1e-3, "{normal} != {}", expected.normal);
+            assert!(batch.velocities[i].distance(expected.velocity) < 1e-3);
+        }
+        if lane_width == LaneWidth::detect() {
+            assert_eq!(batch.heights, detected.heights);
+        }
+    }
+}
+
+#[test]
+fn no_waves_give_flat_still_water() {
+    let settings = HeightQuerySettings::default();
+    let (xs, zs) = sample_points();
+    let mut heights = vec![1.0; xs.len()];
+    get_displaced_wave_heights(&xs, &zs, &[], 1.5, &settings, &mut heights);
+    assert!(heights.iter().all(|&height| height == 0.0));
+
+    // Reusing a batch last filled from real waves
+    let mut batch = WaterSampleBatch::default();
+    sample_water_batch(&xs, &zs, &WaterWaves::default().waves, 1.5, &settings, &mut batch);
+    for lane_width in LaneWidth::ALL {
+        sample_water_batch_with_lanes(lane_width, &xs[..5], &zs[..5], &[], 1.5, &settings, &mut batch);
+        assert_eq!(batch.heights, [0.0; 5]);
+        assert_eq!(batch.normals, [Vec3::Y; 5]);
+        assert_eq!(batch.velocities, [Vec3::ZERO; 5]);
+    }
+}
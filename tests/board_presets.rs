@@ -0,0 +1,102 @@
+mod common;
+
+use bevy::prelude::*;
+use isosurf::{board_presets::BoardPreset, surfboard::FloatingBody};
+
+#[test]
+fn presets_match_their_boards() {
+    let litres = |preset: BoardPreset| preset.surfboard().volume_litres();
+    for preset in BoardPreset::ALL {
+        let (surfboard, floating_body, hull) = preset.bundle();
+        let volume = surfboard.volume();
+        assert!((hull.volume() - volume).abs() < 1e-6, "{preset:?}");
+        let point_volume: f32 = floating_body.point_volumes.iter().sum();
+        assert!((point_volume - volume).abs() < 1e-4 * volume, "{preset:?}: {point_volume} != {volume}");
+        // Foam and glass: a good deal lighter than water
+        let mass = surfboard.mass_properties(floating_body.body_density).mass;
+        assert!((mass - preset.mass()).abs() < 1e-3 * preset.mass(), "{preset:?}: {mass} kg");
+        assert!(floating_body.body_density < 0.2 * floating_body.water_density, "{preset:?}");
+    }
+
+    // Litres in the range such boards are sold with
+    assert!((25.0..40.0).contains(&litres(BoardPreset::Shortboard)));
+    assert!((65.0..90.0).contains(&litres(BoardPreset::Longboard)));
+    assert!(litres(BoardPreset::Sup) > 150.0);
+    assert!(litres(BoardPreset::Sup) > litres(BoardPreset::Longboard));
+    assert!(litres(BoardPreset::Longboard) > litres(BoardPreset::Funboard));
+    assert!(litres(BoardPreset::Funboard) > litres(BoardPreset::Gun));
+    assert!(litres(BoardPreset::Gun) > litres(BoardPreset::Shortboard));
+    assert!(litres(BoardPreset::Fish) > litres(BoardPreset::Shortboard));
+}
+
+#[test]
+fn riders_weigh_boards_down() {
+    let rider = 80.0;
+    let boards = [
+        (BoardPreset::Sup, 0.0),
+        (BoardPreset::Sup, rider / 2.0),
+        (BoardPreset::Sup, rider),
+        (BoardPreset::Shortboard, 0.0),
+        (BoardPreset::Shortboard, rider),
+    ];
+
+    let mut app = common::calm_water(1.0 / 64.0);
+    let mut offset = 0.0;
+    let entities = boards.map(|(preset, rider_mass)| {
+        offset += 5.0;
+        let (surfboard, floating_body, hull) = preset.bundle();
+        let floating_body = FloatingBody { rider_mass, ..floating_body };
+        let transform = Transform::from_xyz(0.0, 0.2, offset);
+        app.world_mut().spawn((transform, surfboard, floating_body, hull)).id()
+    });
+
+    for _ in 0..1500 {
+        app.update();
+    }
+    // Averaged over a few bobs, which a heavy rider damps out slowly
+    let ticks = 128;
+    let mut settled = [(0.0, 0.0); 5];
+    for _ in 0..ticks {
+        app.update();
+        let world = app.world();
+        for (average, entity) in settled.iter_mut().zip(entities) {
+            average.0 += world.get::<Transform>(entity).unwrap().translation.y / ticks as f32;
+            average.1 += world.get::<FloatingBody>(entity).unwrap().submerged_volume / ticks as f32;
+        }
+    }
+    let [(sup, sup_displaced), (sup_light, sup_light_displaced), (sup_ridden, sup_ridden_displaced), ..] = settled;
+    let [.., (shortboard, shortboard_displaced), (sunk, _)] = settled;
+
+    // Floating boards displace their own weight and their rider's
+    let displaced = [sup_displaced, sup_light_displaced, sup_ridden_displaced, shortboard_displaced];
+    for ((preset, rider_mass), displaced) in boards.into_iter().zip(displaced) {
+        let weight = preset.mass() + rider_mass;
+        assert!((displaced * 1000.0 - weight).abs() < 1e-2 * weight, "{preset:?} displaced {displaced} m³");
+    }
+    // Sitting deeper the heavier the rider
+    let sup_thickness = BoardPreset::Sup.surfboard().thickness;
+    assert!(sup - sup_light > sup_thickness / 8.0, "{sup} vs {sup_light}");
+    assert!(sup_light - sup_ridden > sup_thickness / 8.0, "{sup_light} vs {sup_ridden}");
+    // Yet wide enough to stay deck up with the rider's weight a metre above it
+    let deck = app.world().get::<Transform>(entities[2]).unwrap().rotation * Vec3::Y;
+    assert!(deck.y > 0.99, "{deck}");
+    assert!(shortboard.abs() < BoardPreset::Shortboard.surfboard().thickness / 2.0, "{shortboard}");
+    // A shortboard hasn't the volume to carry a standing rider
+    assert!(sunk < -0.5, "{sunk}");
+}
+
+#[test]
+fn riders_sit_as_each_board_is_ridden() {
+    let rider_offset = |preset: BoardPreset| preset.floating_body().rider_offset;
+    for preset in BoardPreset::ALL {
+        let (surfboard, floating_body, _) = preset.bundle();
+        let above_deck = floating_body.rider_offset.y - surfboard.thickness / 2.0;
+        assert!((above_deck - preset.rider_height()).abs() < 1e-6, "{preset:?}: {above_deck}");
+        assert_eq!(floating_body.rider_offset.xz(), Vec2::ZERO, "{preset:?}");
+    }
+
+    // Prone on a bodyboard, crouched on a shortboard and upright on a paddleboard
+    assert!(rider_offset(BoardPreset::Bodyboard).y < 0.3);
+    assert!(rider_offset(BoardPreset::Shortboard).y < rider_offset(BoardPreset::Sup).y);
+    assert!(rider_offset(BoardPreset::Sup).y > 1.0);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use bevy::prelude::*;
+use isosurf::{
+    buoyancy_points::BuoyancyPointsBuilder,
+    surfboard::{FloatingBody, Surfboard},
+};
+
+#[test]
+fn points_fill_each_shape_with_its_volume() {
+    let surfboard = Surfboard::default();
+    let board = Cuboid::new(surfboard.length, surfboard.thickness, surfboard.width);
+    let capsule = Capsule3d::new(0.3, 1.0);
+    let sphere = Sphere::new(0.5);
+    let shapes = [
+        (BuoyancyPointsBuilder::cuboid(board), board.volume(), board.half_size),
+        (BuoyancyPointsBuilder::mesh(&Mesh::from(board)).unwrap(), board.volume(), board.half_size),
+        (BuoyancyPointsBuilder::capsule(capsule), capsule.volume(), Vec3::new(0.3, 0.8, 0.3)),
+        (BuoyancyPointsBuilder::sphere(sphere), sphere.volume(), Vec3::splat(0.5)),
+    ];
+
+    for (builder, volume, half_size) in shapes {
+        let (points, volumes, _) = builder.with_spacing(0.2).points();
+        assert_eq!(points.len(), volumes.len());
+        assert!(points.iter().all(|point| point.abs().cmple(half_size).all()));
+        assert!(volumes.iter().all(|&volume| volume > 0.0));
+
+        let total: f32 = volumes.iter().sum();
+        assert!((total - volume).abs() < 1e-4 * volume, "{total} != {volume}");
+        // Symmetric shapes balance about their centre
+        let centroid = points.iter().zip(&volumes).map(|(point, volume)| *point * *volume).sum::<Vec3>() / total;
+        assert!(centroid.length() < 1e-3, "{centroid}");
+    }
+
+    // Denser sampling, more points; the board is thinner than the spacing so it gets one layer
+    let (coarse, _, _) = BuoyancyPointsBuilder::sphere(sphere).with_spacing(0.25).points();
+    let (fine, _, _) = BuoyancyPointsBuilder::sphere(sphere).with_spacing(0.1).points();
+    assert!(fine.len() > 4 * coarse.len());
+    let (_, _, height) = BuoyancyPointsBuilder::cuboid(board).points();
+    assert!((height - surfboard.thickness).abs() < 1e-6);
+}
+
+#[test]
+fn ball_floats_with_just_a_floating_body() {
+    let sphere = Sphere::new(0.3);
+    let floating_body = FloatingBody {
+        body_density: 500.0,
+        drag_coefficient: 5.0,
+        ..BuoyancyPointsBuilder::sphere(sphere).with_spacing(0.1).build()
+    };
+
+    let mut app = common::calm_water(1.0 / 64.0);
+    app.world_mut().spawn((Transform::from_xyz(0.0, 1.0, 0.0), floating_body));
+
+    for _ in 0..1000 {
+        app.update();
+    }
+
+    // Half as dense as water: half of it under
+    let world = app.world_mut();
+    let (transform, floating_body) = world.query::<(&Transform, &FloatingBody)>().single(world).unwrap();
+    let submerged = floating_body.submerged_volume / sphere.volume();
+    assert!((submerged - 0.5).abs() < 0.01, "{submerged} submerged");
+    assert!(transform.translation.y.abs() < 0.05, "{}", transform.translation);
+}
+
+#[test]
+fn extra_points_share_the_default_volume() {
+    let volume: f32 = FloatingBody::default().point_volumes.iter().sum();
+    let buoyancy_points = (0..9)
+        .map(|i| Vec3::new((i % 3) as f32 - 1.0, 0.0, (i / 3) as f32 * 0.25 - 0.25))
+        .collect();
+    let mut app = common::calm_water(1.0 / 64.0);
+    app.world_mut()
+        .spawn((Transform::IDENTITY, FloatingBody { buoyancy_points, ..default() }));
+
+    app.update();
+    app.update();
+
+    // Every point carries a share rather than those past the fifth floating nothing
+    let world = app.world_mut();
+    let floating_body = world.query::<&FloatingBody>().single(world).unwrap();
+    assert_eq!(floating_body.point_volumes.len(), 9);
+    let total: f32 = floating_body.point_volumes.iter().sum();
+    assert!((total - volume).abs() < 1e-6 * volume, "{total} != {volume}");
+}
+
+#[test]
+#[should_panic(expected = "spacing must be positive")]
+fn zero_spacing_is_rejected() {
+    BuoyancyPointsBuilder::sphere(Sphere::new(0.5)).with_spacing(0.0);
+}
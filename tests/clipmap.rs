@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use isosurf::{
+    clipmap::{WaterClipmap, create_clipmap_mesh, stitch_seams},
+    water::{BasePositions, WaterWaves, displace_water_surface},
+};
+
+fn triangles(clipmap: &WaterClipmap) -> (Vec<Vec3>, Vec<u32>) {
+    let mut positions = Vec::new();
+    clipmap.base_positions(&mut positions);
+    (positions, clipmap.indices())
+}
+
+#[test]
+fn levels_tile_without_gaps_or_overlaps() {
+    let mut clipmap = WaterClipmap::new(4, 16, 1.0);
+
+    for focus in [Vec2::ZERO, Vec2::new(0.6, -0.6), Vec2::new(13.2, 7.9), Vec2::new(-101.0, 55.5)] {
+        clipmap.recentre(focus);
+        let (positions, indices) = triangles(&clipmap);
+
+        let area: f64 = indices
+            .chunks(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize].xz().as_dvec2());
+                (b - a).perp_dot(c - a).abs() / 2.0
+            })
+            .sum();
+        let extent = clipmap.extent() as f64;
+        assert!((area - extent * extent).abs() < 1e-6, "area {area} != {}", extent * extent);
+
+        // The coarsest level is centred on the focus to within one snap step
+        let offset = clipmap.level_centre(3) - focus;
+        assert!(offset.abs().max_element() <= clipmap.level_cell_size(3));
+    }
+}
+
+#[test]
+fn levels_snap_to_whole_grid_steps() {
+    let mut clipmap = WaterClipmap::default();
+    clipmap.recentre(Vec2::ZERO);
+    assert!(!clipmap.recentre(Vec2::new(0.1, -0.2)), "moving within a cell must not shift the grid");
+    assert!(clipmap.recentre(Vec2::new(0.6, 0.0)));
+
+    let mut positions = Vec::new();
+    clipmap.base_positions(&mut positions);
+    for (level, level_positions) in positions.chunks(clipmap.vertices_per_level()).enumerate() {
+        let cell_size = clipmap.level_cell_size(level);
+        for position in level_positions {
+            let cells = position.xz() / cell_size;
+            assert_eq!(cells, cells.round(), "level {level} vertex {position} is off its grid");
+        }
+    }
+}
+
+#[test]
+fn displaced_levels_meet_without_cracks() {
+    let mut clipmap = WaterClipmap::new(3, 16, 0.75);
+    clipmap.recentre(Vec2::new(4.3, -2.9));
+    let (_, base_positions) = create_clipmap_mesh(&clipmap);
+
+    let (mut pos_data, mut normals, mut tangents) = (
+        vec![[0.0; 3]; base_positions.len()],
+        vec![[0.0; 3]; base_positions.len()],
+        vec![[0.0; 4]; base_positions.len()],
+    );
+    let base_positions_soa = BasePositions::new(&base_positions);
+    let waves = WaterWaves::default().waves;
+    displace_water_surface(&base_positions_soa, &waves, 3.7, &mut pos_data, &mut normals, &mut tangents);
+    let seams = clipmap.seam_vertices();
+    stitch_seams(&seams, &mut pos_data, &mut normals, &mut tangents);
+
+    // Vertices sitting where two levels touch must be displaced identically
+    let mut shared: HashMap<[u32; 2], Vec<usize>> = HashMap::new();
+    for (i, pos) in base_positions.iter().enumerate() {
+        shared.entry([pos.x.to_bits(), pos.z.to_bits()]).or_default().push(i);
+    }
+    let vertices_per_level = clipmap.vertices_per_level();
+    let coarser_vertex = |vertex: u32| {
+        let pos = base_positions[vertex as usize];
+        *shared[&[pos.x.to_bits(), pos.z.to_bits()]]
+            .iter()
+            .find(|&&i| i / vertices_per_level > vertex as usize / vertices_per_level)
+            .expect("seam endpoint has no matching vertex in the coarser level")
+    };
+
+    for [vertex, start, end] in seams {
+        let coarse_start = Vec3::from(pos_data[coarser_vertex(start)]);
+        let coarse_end = Vec3::from(pos_data[coarser_vertex(end)]);
+        assert!(Vec3::from(pos_data[start as usize]).distance(coarse_start) < 1e-4);
+        assert!(Vec3::from(pos_data[end as usize]).distance(coarse_end) < 1e-4);
+
+        let on_edge = coarse_start.midpoint(coarse_end);
+        assert!(Vec3::from(pos_data[vertex as usize]).distance(on_edge) < 1e-4);
+
+        // and shaded like the coarse edge, not like the fine surface they were lifted off
+        let edge_normal = Vec3::from(normals[coarser_vertex(start)]) + Vec3::from(normals[coarser_vertex(end)]);
+        assert!(Vec3::from(normals[vertex as usize]).distance(edge_normal.normalize()) < 1e-4);
+    }
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use isosurf::{
+    surfboard::update_surfboard_physics,
+    water::{HeightQuerySettings, WaterWaves},
+};
+
+/// Flat calm water at y = 0 running the floating body physics, stepped `dt` per update
+pub fn calm_water(dt: f32) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(dt)))
+        .init_resource::<HeightQuerySettings>()
+        .add_systems(Update, update_surfboard_physics);
+    app.world_mut()
+        .spawn((Transform::IDENTITY, GlobalTransform::IDENTITY, WaterWaves { waves: Vec::new() }));
+    app
+}
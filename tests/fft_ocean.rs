@@ -0,0 +1,105 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use isosurf::{
+    fft_ocean::{FftOcean, FftOceanSettings},
+    water::{HeightQuerySettings, WaterWaves},
+    water_query::WaterQuery,
+};
+
+/// Height at every grid point of one patch
+fn heights(ocean: &FftOcean) -> Vec<f32> {
+    let settings = ocean.settings();
+    let cell = settings.patch_size / settings.resolution as f32;
+    (0..settings.resolution * settings.resolution)
+        .map(|index| {
+            let (x, z) = (index % settings.resolution, index / settings.resolution);
+            ocean.height_at(Vec2::new(x as f32, z as f32) * cell)
+        })
+        .collect()
+}
+
+fn rms(heights: &[f32]) -> f32 {
+    (heights.iter().map(|height| height * height).sum::<f32>() / heights.len() as f32).sqrt()
+}
+
+#[test]
+fn same_seed_gives_the_same_sea() {
+    let settings = FftOceanSettings {
+        seed: 42,
+        ..default()
+    };
+    let (mut first, mut second) = (FftOcean::new(settings), FftOcean::new(settings));
+    first.evolve(3.5);
+    second.evolve(3.5);
+    assert_eq!(heights(&first), heights(&second));
+    let position = Vec2::new(12.3, -45.6);
+    assert_eq!(first.displacement_at(position), second.displacement_at(position));
+
+    let mut reseeded = FftOcean::new(FftOceanSettings { seed: 43, ..settings });
+    reseeded.evolve(3.5);
+    assert_ne!(heights(&first), heights(&reseeded));
+}
+
+#[test]
+fn field_is_a_tiling_zero_mean_sea_that_grows_with_the_wind() {
+    let mut ocean = FftOcean::default();
+    let calm = heights(&ocean);
+    let mean = calm.iter().sum::<f32>() / calm.len() as f32;
+    assert!(calm.iter().all(|height| height.is_finite()));
+    assert!(rms(&calm) > 0.01, "{}", rms(&calm));
+    assert!(mean.abs() < 1e-3 * rms(&calm).max(1.0), "mean {mean}");
+
+    // Tiles every patch, and moves over time
+    let patch_size = ocean.settings().patch_size;
+    let position = Vec2::new(3.7, 8.1);
+    let tiled = ocean.displacement_at(position + Vec2::new(patch_size, -2.0 * patch_size));
+    assert!(ocean.displacement_at(position).distance(tiled) < 1e-4);
+    ocean.evolve(2.0);
+    assert_ne!(heights(&ocean), calm);
+
+    // Weaker wind, shorter and lower waves
+    let breeze = FftOcean::new(FftOceanSettings {
+        wind_speed: 4.0,
+        ..default()
+    });
+    assert!(rms(&calm) > 2.0 * rms(&heights(&breeze)));
+}
+
+#[test]
+fn samples_slope_and_move_with_the_fft_heights() {
+    // The surface velocity is the displacement's rate of change
+    let mut ocean = FftOcean::default();
+    let (time, dt) = (4.0, 1e-3);
+    let position = Vec2::new(17.3, -6.2);
+    ocean.evolve(time - dt);
+    let before = ocean.displacement_at(position);
+    ocean.evolve(time + dt);
+    let after = ocean.displacement_at(position);
+    ocean.evolve(time);
+    let velocity = ocean.velocity_at(position);
+    let expected = (after - before) / (2.0 * dt);
+    assert!(velocity.distance(expected) < 1e-2 * expected.length().max(0.1), "{velocity} != {expected}");
+
+    // Together with Gerstner waves on one body, the normal follows the blended heights
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).init_resource::<HeightQuerySettings>();
+    app.world_mut()
+        .spawn((Transform::IDENTITY, GlobalTransform::IDENTITY, WaterWaves::default(), ocean));
+    app.update();
+    let checks = app
+        .world_mut()
+        .run_system_once(|water: WaterQuery| {
+            let step = 1e-3;
+            [Vec3::new(3.4, 0.0, 7.9), Vec3::new(-21.0, 0.0, 40.7), Vec3::new(55.5, 0.0, -12.2)].map(|point| {
+                let height = |offset: Vec3| water.height(point + offset).unwrap();
+                let slope = Vec2::new(
+                    height(Vec3::X * step) - height(Vec3::NEG_X * step),
+                    height(Vec3::Z * step) - height(Vec3::NEG_Z * step),
+                ) / (2.0 * step);
+                (water.sample(point).unwrap().gradient, slope)
+            })
+        })
+        .unwrap();
+    for (gradient, slope) in checks {
+        assert!(gradient.distance(slope) < 2e-2 * slope.length().max(0.1), "{gradient} != {slope}");
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use isosurf::{
+    spectrum::{OceanSpectrum, SpectrumSampling},
+    water::{
+        BasePositions, HeightQuerySettings, WaterWaves, WaveParameters, create_water_mesh, displace_water_vertices,
+        find_undisplaced_position, get_displaced_wave_height, get_wave_height,
+    },
+};
+
+const TOLERANCE: f32 = 1e-3;
+
+fn displaced_mesh(waves: &[WaveParameters], time: f32) -> Vec<[f32; 3]> {
+    let (_, base_positions) = create_water_mesh(64, 100.0);
+    let mut positions = vec![[0.0; 3]; base_positions.len()];
+    displace_water_vertices(&BasePositions::new(&base_positions), waves, time, &mut positions);
+    positions
+}
+
+fn max_height_error(waves: &[WaveParameters], time: f32, settings: &HeightQuerySettings) -> f32 {
+    displaced_mesh(waves, time)
+        .iter()
+        .map(|&[x, y, z]| (get_displaced_wave_height(Vec2::new(x, z), waves, time, settings) - y).abs())
+        .fold(0.0, f32::max)
+}
+
+fn steep_wave() -> WaveParameters {
+    WaveParameters {
+        amplitude: 1.0,
+        wavelength: 10.0,
+        speed: 2.5,
+        direction: Vec2::new(1.0, 0.4).normalize(),
+        steepness: 0.8 / (2.0 * std::f32::consts::PI / 10.0),
+        wave_number: 2.0 * std::f32::consts::PI / 10.0,
+        phase: 0.3,
+    }
+}
+
+#[test]
+fn default_waves_match_displaced_mesh() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+
+    for time in [0.0, 1.7, 12.3] {
+        let error = max_height_error(&waves.waves, time, &settings);
+        assert!(error < TOLERANCE, "max error {error} at t={time}");
+    }
+}
+
+#[test]
+fn spectrum_waves_match_displaced_mesh() {
+    let waves = WaterWaves::from_spectrum(&OceanSpectrum::default(), &SpectrumSampling::default());
+    let settings = HeightQuerySettings::default();
+
+    let error = max_height_error(&waves.waves, 4.2, &settings);
+    assert!(error < TOLERANCE, "max error {error}");
+}
+
+#[test]
+fn steep_crests_match_displaced_mesh() {
+    let waves = [steep_wave()];
+    let settings = HeightQuerySettings {
+        max_iterations: 16,
+        ..default()
+    };
+
+    let error = max_height_error(&waves, 0.9, &settings);
+    assert!(error < TOLERANCE, "max error {error}");
+}
+
+#[test]
+fn undisplaced_height_misses_steep_crests() {
+    let waves = [steep_wave()];
+    let time = 0.9;
+
+    let naive_error = displaced_mesh(&waves, time)
+        .iter()
+        .map(|&[x, y, z]| (get_wave_height(Vec2::new(x, z), &waves, time) - y).abs())
+        .fold(0.0, f32::max);
+    assert!(naive_error > 0.1, "naive error {naive_error} should be visible");
+}
+
+#[test]
+fn zero_iterations_returns_target() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings {
+        max_iterations: 0,
+        ..default()
+    };
+    let target = Vec2::new(3.0, -7.5);
+
+    assert_eq!(find_undisplaced_position(target, &waves.waves, 2.0, &settings), target);
+}
+
+#[test]
+fn undisplaced_position_round_trips() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    let time = 5.0;
+
+    for base in [Vec2::ZERO, Vec2::new(12.0, -4.0), Vec2::new(-31.5, 20.25)] {
+        let mut displaced = [[0.0; 3]];
+        let base_positions = BasePositions::new(&[Vec3::new(base.x, 0.0, base.y)]);
+        displace_water_vertices(&base_positions, &waves.waves, time, &mut displaced);
+        let [x, _, z] = displaced[0];
+
+        let solved = find_undisplaced_position(Vec2::new(x, z), &waves.waves, time, &settings);
+        assert!(solved.distance(base) < TOLERANCE, "{solved} != {base}");
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use bevy::prelude::*;
+use isosurf::{
+    hull_buoyancy::BuoyancyHull,
+    surfboard::{FloatingBody, Surfboard, create_surfboard_mesh},
+};
+
+fn submerged_in_calm_water(hull: &BuoyancyHull, transform: Transform, water_height: f32) -> (f32, Vec3) {
+    let world_vertices: Vec<Vec3> = hull.vertices.iter().map(|&vertex| transform.transform_point(vertex)).collect();
+    let water_heights = vec![water_height; world_vertices.len()];
+    let submerged = hull.submerged(&world_vertices, &water_heights, &mut Vec::new());
+    (submerged.volume, submerged.centre)
+}
+
+#[test]
+fn clipped_hull_gives_the_submerged_volume_and_centre() {
+    let slab = Cuboid::new(3.0, 0.1, 0.6);
+    let hull = BuoyancyHull::from_mesh(&Mesh::from(slab)).unwrap();
+    let volume = slab.volume();
+    let thickness = 0.1;
+    assert!((hull.volume() - volume).abs() < 1e-6 * volume.max(1.0));
+
+    let (above, _) = submerged_in_calm_water(&hull, Transform::from_xyz(0.0, 1.0, 0.0), 0.0);
+    assert_eq!(above, 0.0);
+
+    let (below, centre) = submerged_in_calm_water(&hull, Transform::from_xyz(2.0, -1.0, 3.0), 0.0);
+    assert!((below - volume).abs() < 1e-5);
+    assert!(centre.distance(Vec3::new(2.0, -1.0, 3.0)) < 1e-5);
+
+    // Bottom quarter of the thickness under water
+    let draft = thickness / 4.0;
+    let transform = Transform::from_xyz(0.0, thickness / 2.0 - draft, 0.0);
+    let (quarter, centre) = submerged_in_calm_water(&hull, transform, 0.0);
+    assert!((quarter - volume / 4.0).abs() < 1e-5, "{quarter}");
+    assert!(centre.distance(Vec3::new(0.0, -draft / 2.0, 0.0)) < 1e-5, "{centre}");
+
+    // Rolled onto its rail with the centre at the waterline: half the board by symmetry, with the
+    // centre of buoyancy out towards the submerged rail
+    let rolled = Transform::from_rotation(Quat::from_rotation_x(0.3));
+    let (half, centre) = submerged_in_calm_water(&hull, rolled, 0.0);
+    assert!((half - volume / 2.0).abs() < 1e-5, "{half}");
+    assert!(centre.y < 0.0 && centre.z.abs() > 0.1, "{centre}");
+}
+
+#[test]
+fn shaped_board_floats_on_its_hull() {
+    let surfboard = Surfboard::default();
+    let hull = BuoyancyHull::from_mesh(&create_surfboard_mesh(&surfboard)).unwrap();
+    assert!((hull.volume() - surfboard.volume()).abs() < 1e-6);
+    // Settles under the hydrodynamic drag on the hull alone
+    let floating_body = FloatingBody::default();
+    let mass = surfboard.mass_properties(floating_body.body_density).mass;
+
+    let mut app = common::calm_water(1.0 / 64.0);
+    let transform = Transform::from_xyz(0.0, 0.3, 0.0).with_rotation(Quat::from_rotation_x(0.2));
+    app.world_mut().spawn((transform, floating_body, surfboard.clone(), hull));
+
+    for _ in 0..1500 {
+        app.update();
+    }
+
+    // Displacing its own weight of water, sitting in it
+    let world = app.world_mut();
+    let (transform, floating_body) = world.query::<(&Transform, &FloatingBody)>().single(world).unwrap();
+    let displaced = floating_body.submerged_volume * floating_body.water_density;
+    assert!((displaced - mass).abs() < 1e-3 * mass, "{displaced} kg displaced by {mass} kg");
+    assert!(transform.translation.y.abs() < surfboard.thickness / 2.0, "{}", transform.translation);
+    // Righted itself from the initial roll, whichever way it ended up facing
+    let tilt = (transform.rotation * Vec3::Z).y.abs();
+    assert!(tilt < 1e-2, "rolled {tilt}");
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use isosurf::{
+    hull_buoyancy::{BuoyancyHull, SubmergedTriangle},
+    hydrodynamics::Hydrodynamics,
+    rigid_body::Velocity,
+};
+
+const DT: f32 = 1.0 / 64.0;
+const LENGTH: f32 = 3.0;
+
+/// Flat 3 m slab of a board clipped against calm water at y = 0
+fn clipped_board(transform: Transform) -> (BuoyancyHull, Vec<SubmergedTriangle>) {
+    let hull = BuoyancyHull::from_mesh(&Mesh::from(Cuboid::new(LENGTH, 0.1, 0.6))).unwrap();
+    let world_vertices: Vec<Vec3> = hull.vertices.iter().map(|&vertex| transform.transform_point(vertex)).collect();
+    let mut clipped = Vec::new();
+    hull.submerged(&world_vertices, &vec![0.0; world_vertices.len()], &mut clipped);
+    (hull, clipped)
+}
+
+fn forces(
+    hydrodynamics: &Hydrodynamics,
+    (hull, clipped): &(BuoyancyHull, Vec<SubmergedTriangle>),
+    swept: &mut Vec<f32>,
+    centre: Vec3,
+    velocity: Velocity,
+    water_velocity: Vec3,
+) -> (Vec3, Vec3) {
+    let water_velocities = vec![water_velocity; clipped.len()];
+    hydrodynamics.hull_forces(hull, swept, clipped, &water_velocities, centre, &velocity, 36.0, LENGTH, 1000.0, DT)
+}
+
+#[test]
+fn drag_opposes_motion_through_the_water() {
+    let hydrodynamics = Hydrodynamics {
+        planing_lift: 0.0,
+        ..default()
+    };
+    let centre = Vec3::new(0.0, -1.0, 0.0);
+    let board = clipped_board(Transform::from_translation(centre));
+    let mut swept = Vec::new();
+    let moving = |linear: Vec3| Velocity { linear, ..default() };
+
+    let mut previous_drag = 0.0;
+    for speed in [0.5, 1.0, 2.0, 4.0] {
+        for direction in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, 0.0, 1.0).normalize()] {
+            let velocity = moving(direction * speed);
+            let (force, _) = forces(&hydrodynamics, &board, &mut swept, centre, velocity, Vec3::ZERO);
+            assert!(force.dot(direction) < 0.0, "{force} along {direction}");
+        }
+        // Pressure drag grows faster than linearly with speed
+        let velocity = moving(Vec3::X * speed);
+        let (force, torque) = forces(&hydrodynamics, &board, &mut swept, centre, velocity, Vec3::ZERO);
+        assert!(-force.x > previous_drag * 2.0, "{} at {speed} m/s", -force.x);
+        assert!(torque.length() < 1e-3 * force.length());
+        previous_drag = -force.x;
+    }
+
+    // Riding along with the water's orbital motion there is nothing to resist
+    let orbital = Vec3::new(0.7, 0.3, -0.2);
+    let (force, torque) = forces(&hydrodynamics, &board, &mut swept, centre, moving(orbital), orbital);
+    assert!(force.length() < 1e-4 && torque.length() < 1e-4);
+    // While a body at rest in a current gets carried along with it
+    let (force, _) = forces(&hydrodynamics, &board, &mut swept, centre, moving(Vec3::ZERO), orbital);
+    assert!(force.dot(orbital) > 0.0);
+}
+
+#[test]
+fn planing_and_slamming_push_the_board_up() {
+    // Nose up, gliding forward along the surface
+    let attack = 0.1;
+    let transform = Transform::from_xyz(0.0, 0.02, 0.0).with_rotation(Quat::from_rotation_z(attack));
+    let board = clipped_board(transform);
+    let mut swept = Vec::new();
+    let gliding = Velocity {
+        linear: Vec3::X * 5.0,
+        ..default()
+    };
+    let planing = Hydrodynamics::default();
+    let no_lift = Hydrodynamics {
+        planing_lift: 0.0,
+        ..default()
+    };
+    let (lifted, _) = forces(&planing, &board, &mut swept, transform.translation, gliding, Vec3::ZERO);
+    let (unlifted, _) = forces(&no_lift, &board, &mut swept, transform.translation, gliding, Vec3::ZERO);
+    // Enough to carry the board and a rider
+    assert!(lifted.y - unlifted.y > 1000.0, "{lifted} vs {unlifted}");
+
+    // Dropping flat onto the water: the first tick in slams, a steady descent does not
+    let falling = Velocity {
+        linear: Vec3::NEG_Y * 3.0,
+        ..default()
+    };
+    let board = clipped_board(Transform::from_xyz(0.0, 0.04, 0.0));
+    let mut swept = Vec::new();
+    let (entry, _) = forces(&no_lift, &board, &mut swept, Vec3::ZERO, falling, Vec3::ZERO);
+    let (steady, _) = forces(&no_lift, &board, &mut swept, Vec3::ZERO, falling, Vec3::ZERO);
+    assert!(steady.y > 0.0);
+    assert!(entry.y > steady.y + 100.0, "{entry} vs {steady}");
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use isosurf::{
+    simd::{LaneWidth, SimdF32, f32x16},
+    water::{
+        BasePositions, WaterWaves, displace_water_surface_with_lanes, displace_water_vertices_with_lanes,
+        get_wave_displacement, sample_gerstner_surface,
+    },
+};
+
+fn base_positions(count: usize) -> BasePositions {
+    let positions: Vec<Vec3> = (0..count)
+        .map(|i| Vec3::new(i as f32 * 1.7 - 30.0, 0.0, (i as f32 * 0.9).sin() * 25.0))
+        .collect();
+    BasePositions::new(&positions)
+}
+
+#[test]
+fn every_lane_width_displaces_the_same_surface() {
+    let waves = WaterWaves::default();
+    let time = 9.1;
+
+    // Counts below, at and between every lane width so each padding case is hit
+    for count in [1, 3, 4, 7, 8, 13, 16, 37] {
+        let base_positions = base_positions(count);
+
+        for lane_width in LaneWidth::ALL {
+            let mut positions = vec![[0.0; 3]; count];
+            displace_water_vertices_with_lanes(lane_width, &base_positions, &waves.waves, time, &mut positions);
+
+            let mut surface_positions = vec![[0.0; 3]; count];
+            let mut normals = vec![[0.0; 3]; count];
+            let mut tangents = vec![[0.0; 4]; count];
+            displace_water_surface_with_lanes(
+                lane_width,
+                &base_positions,
+                &waves.waves,
+                time,
+                &mut surface_positions,
+                &mut normals,
+                &mut tangents,
+            );
+
+            for (i, base_pos) in base_positions.iter().enumerate() {
+                let expected = base_pos + get_wave_displacement(base_pos.xz(), &waves.waves, time);
+                let sample = sample_gerstner_surface(base_pos.xz(), &waves.waves, time);
+
+                let position = Vec3::from(positions[i]);
+                assert!(position.distance(expected) < 1e-4, "{lane_width:?} [{i}]: {position} != {expected}");
+                assert!(Vec3::from(surface_positions[i]).distance(expected) < 1e-4);
+                assert!(Vec3::from(normals[i]).distance(sample.normal) < 1e-4, "{lane_width:?} [{i}/{count}]");
+            }
+        }
+    }
+}
+
+#[test]
+fn sixteen_lanes_load_pad_and_store() {
+    let values: Vec<f32> = (0..11).map(|i| i as f32).collect();
+    let lanes = f32x16::load(&values);
+
+    let mut out = [0.0; 16];
+    lanes.store(&mut out);
+    assert_eq!(out[..11], values[..]);
+    assert!(out[11..].iter().all(|value| *value == 10.0), "missing lanes repeat the last value: {out:?}");
+
+    let (sin, cos) = lanes.sin_cos();
+    let mut sines = [0.0; 16];
+    let mut cosines = [0.0; 16];
+    sin.store(&mut sines);
+    cos.store(&mut cosines);
+    for (value, (sin, cos)) in out.iter().zip(sines.iter().zip(&cosines)) {
+        assert!((sin - value.sin()).abs() < 1e-5 && (cos - value.cos()).abs() < 1e-5);
+    }
+
+    let mask = lanes.lt(f32x16::splat(5.0));
+    let selected = mask.select(f32x16::splat(1.0), f32x16::splat(0.0));
+    let mut flags = [0.0; 16];
+    selected.store(&mut flags);
+    assert_eq!(flags.iter().sum::<f32>(), 5.0);
+    assert!(!mask.all() && lanes.lt(f32x16::splat(100.0)).all());
+}
+
+#[test]
+fn detected_width_follows_the_compiled_target_features() {
+    let expected = if cfg!(target_feature = "avx512f") {
+        LaneWidth::X16
+    } else if cfg!(all(target_feature = "avx2", target_feature = "fma")) {
+        LaneWidth::X8
+    } else {
+        LaneWidth::X4
+    };
+    assert_eq!(LaneWidth::detect(), expected);
+
+    // Without AVX in the build `wide` has no 256-bit path, so the kernels must not claim a wider width
+    if !cfg!(target_feature = "avx2") {
+        assert_eq!(LaneWidth::detect().lanes(), 4);
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::{
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPoolBuilder},
+};
+use isosurf::water::{
+    BasePositions, WaterDisplacementSettings, WaterWaves, create_water_mesh, displace_water_surface,
+    displace_water_surface_parallel,
+};
+
+type Surface = (Vec<[f32; 3]>, Vec<[f32; 3]>, Vec<[f32; 4]>);
+
+fn empty_surface(count: usize) -> Surface {
+    (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count])
+}
+
+#[test]
+fn parallel_displacement_matches_single_threaded() {
+    ComputeTaskPool::get_or_init(|| TaskPoolBuilder::new().num_threads(4).build());
+
+    // 63 x 63 vertices, so neither rows nor the whole mesh line up with SIMD chunks
+    let base_positions = BasePositions::new(&create_water_mesh(63, 80.0).1);
+    let waves = WaterWaves::default();
+    let time = 12.5;
+    let count = base_positions.len();
+
+    let (mut positions, mut normals, mut tangents) = empty_surface(count);
+    displace_water_surface(&base_positions, &waves.waves, time, &mut positions, &mut normals, &mut tangents);
+
+    for batch_size in [1, 6, 63, 256, 1000, count, count * 2] {
+        let (mut parallel_positions, mut parallel_normals, mut parallel_tangents) = empty_surface(count);
+        displace_water_surface_parallel(
+            &base_positions,
+            &waves.waves,
+            time,
+            &mut parallel_positions,
+            &mut parallel_normals,
+            &mut parallel_tangents,
+            None,
+            &WaterDisplacementSettings {
+                batch_size,
+                ..default()
+            },
+        );
+
+        // Bit-for-bit identical, not just close
+        assert_eq!(parallel_positions, positions, "positions differ with batch size {batch_size}");
+        assert_eq!(parallel_normals, normals, "normals differ with batch size {batch_size}");
+        assert_eq!(parallel_tangents, tangents, "tangents differ with batch size {batch_size}");
+    }
+}
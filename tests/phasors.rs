@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use isosurf::{
+    spectrum::{OceanSpectrum, SpectrumSampling},
+    water::{
+        BasePositions, WavePhasors, WaterWaves, WaveParameters, create_water_mesh, displace_water_surface,
+        displace_water_surface_from_phasors,
+    },
+};
+
+const DT: f32 = 1.0 / 64.0;
+
+/// Largest distance between the surface from `phasors` and the one evaluated exactly at their time
+fn max_phasor_error(phasors: &WavePhasors, base_positions: &BasePositions, waves: &[WaveParameters]) -> f32 {
+    let count = base_positions.len();
+    let mut exact = (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count]);
+    let mut rotated = exact.clone();
+    displace_water_surface(base_positions, waves, phasors.time(), &mut exact.0, &mut exact.1, &mut exact.2);
+    let (pos_data, normals, tangents) = (&mut rotated.0, &mut rotated.1, &mut rotated.2);
+    displace_water_surface_from_phasors(phasors, base_positions, waves, pos_data, normals, tangents);
+
+    let positions = exact.0.iter().zip(&rotated.0);
+    let normals = exact.1.iter().zip(&rotated.1);
+    positions
+        .chain(normals)
+        .map(|(exact, rotated)| Vec3::from(*exact).distance(Vec3::from(*rotated)))
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn rotated_phasors_track_the_exact_surface() {
+    let spectrum = WaterWaves::from_spectrum(&OceanSpectrum::default(), &SpectrumSampling::default());
+    let base_positions = BasePositions::new(&create_water_mesh(40, 80.0).1);
+
+    for waves in [WaterWaves::default(), spectrum] {
+        // Never resynced, so this is all rotation drift
+        let mut phasors = WavePhasors::new(u32::MAX);
+        let mut time = 3.0;
+        phasors.advance(&base_positions, &waves.waves, time, DT);
+
+        for tick in 1..=2000 {
+            time += DT;
+            phasors.advance(&base_positions, &waves.waves, time, DT);
+            if tick % 250 == 0 {
+                let error = max_phasor_error(&phasors, &base_positions, &waves.waves);
+                assert!(error < 2e-4, "tick {tick}: {error}");
+            }
+        }
+    }
+}
+
+#[test]
+fn phasors_resync_when_they_cannot_rotate() {
+    let mut waves = WaterWaves::default();
+    let base_positions = BasePositions::new(&create_water_mesh(24, 50.0).1);
+    let mut phasors = WavePhasors::new(8);
+    let mut time = 0.0;
+
+    for _ in 0..20 {
+        time += DT;
+        phasors.advance(&base_positions, &waves.waves, time, DT);
+    }
+    assert!(phasors.matches(&base_positions, &waves.waves));
+
+    // New waves
+    waves.waves[1].direction = Vec2::from_angle(0.5).rotate(waves.waves[1].direction);
+    assert!(!phasors.matches(&base_positions, &waves.waves));
+    time += DT;
+    phasors.advance(&base_positions, &waves.waves, time, DT);
+    assert!(max_phasor_error(&phasors, &base_positions, &waves.waves) < 1e-5);
+
+    // Skipped ticks
+    time += 10.0;
+    phasors.advance(&base_positions, &waves.waves, time, DT);
+    assert_eq!(phasors.time(), time);
+    assert!(max_phasor_error(&phasors, &base_positions, &waves.waves) < 1e-5);
+
+    // New base positions with the same vertex count
+    let mut moved = base_positions.clone();
+    let shifted: Vec<Vec3> = base_positions.iter().map(|pos| pos + Vec3::new(3.0, 0.0, -1.0)).collect();
+    moved.set(&shifted);
+    assert!(!phasors.matches(&moved, &waves.waves));
+    time += DT;
+    phasors.advance(&moved, &waves.waves, time, DT);
+    assert!(max_phasor_error(&phasors, &moved, &waves.waves) < 1e-5);
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use isosurf::{
+    rigid_body::{MassProperties, Velocity},
+    surfboard::{FloatingBody, Surfboard},
+    water::GRAVITY,
+};
+
+/// Flat calm water at y = 0 and one default board, stepped `dt` per update
+fn calm_water_with_board(dt: f32, transform: Transform, floating_body: FloatingBody) -> App {
+    let mut app = common::calm_water(dt);
+    app.world_mut().spawn((transform, floating_body, Surfboard::default()));
+    app
+}
+
+fn board(app: &mut App) -> (Transform, f32) {
+    let world = app.world_mut();
+    let (transform, floating_body) = world.query::<(&Transform, &FloatingBody)>().single(world).unwrap();
+    (*transform, floating_body.submerged_volume)
+}
+
+#[test]
+fn board_settles_at_its_equilibrium_draft() {
+    let floating_body = FloatingBody {
+        drag_coefficient: 10.0,
+        ..default()
+    };
+    let density_ratio = floating_body.body_density / floating_body.water_density;
+    let mut app = calm_water_with_board(1.0 / 64.0, Transform::from_xyz(0.0, 0.3, 0.0), floating_body);
+
+    for _ in 0..1500 {
+        app.update();
+    }
+
+    let surfboard = Surfboard::default();
+    let (transform, submerged_volume) = board(&mut app);
+    let draft = surfboard.thickness / 2.0 - transform.translation.y;
+    let expected_draft = density_ratio * surfboard.thickness;
+    assert!((draft - expected_draft).abs() < 1e-3, "draft {draft} != {expected_draft}");
+
+    // The board displaces its own weight of water
+    let mass = surfboard.mass_properties(density_ratio * 1000.0).mass;
+    assert!((submerged_volume * 1000.0 - mass).abs() < 0.05 * mass, "{submerged_volume} m³ for {mass} kg");
+    assert!(transform.rotation.angle_between(Quat::IDENTITY) < 1e-3);
+}
+
+#[test]
+fn roll_period_matches_the_restoring_stiffness() {
+    let surfboard = Surfboard::default();
+    let floating_body = FloatingBody {
+        drag_coefficient: 0.0,
+        ..default()
+    };
+
+    // Small-angle roll about the long axis: I θ'' = -k θ, with k summed over the buoyancy points
+    let points = floating_body.buoyancy_points.iter().zip(&floating_body.point_volumes);
+    let stiffness: f32 = points
+        .map(|(point, volume)| {
+            let point_area = volume / floating_body.point_height;
+            floating_body.water_density * GRAVITY * point_area * point.z * point.z
+        })
+        .sum();
+    let inertia = surfboard.mass_properties(floating_body.body_density).inertia.x;
+    let expected_period = TAU * (inertia / stiffness).sqrt();
+
+    // Start at the equilibrium draft, heeled over slightly
+    let draft = floating_body.body_density / floating_body.water_density * surfboard.thickness;
+    let transform =
+        Transform::from_xyz(0.0, surfboard.thickness / 2.0 - draft, 0.0).with_rotation(Quat::from_rotation_x(0.02));
+    let dt = 1.0 / 1000.0;
+    let mut app = calm_water_with_board(dt, transform, floating_body);
+
+    let mut upward_crossings = Vec::new();
+    let mut previous_roll = (transform.rotation * Vec3::Z).y;
+    for step in 0..2000 {
+        app.update();
+        let (transform, _) = board(&mut app);
+        let roll = (transform.rotation * Vec3::Z).y;
+        if previous_roll < 0.0 && roll >= 0.0 {
+            upward_crossings.push(step as f32 * dt);
+        }
+        previous_roll = roll;
+    }
+
+    assert!(upward_crossings.len() >= 5, "only {} roll cycles", upward_crossings.len());
+    let cycles = upward_crossings.len() - 1;
+    let period = (upward_crossings[cycles] - upward_crossings[0]) / cycles as f32;
+    assert!((period - expected_period).abs() < 0.03 * expected_period, "roll period {period} != {expected_period}");
+}
+
+#[test]
+fn free_spin_keeps_its_energy_and_momentum() {
+    // A long, thin board tumbling fast about an axis near its unstable intermediate one
+    let mass = MassProperties::cuboid(200.0, Vec3::new(2.0, 0.1, 0.5));
+    let mut transform = Transform::IDENTITY;
+    let mut velocity = Velocity {
+        angular: Vec3::new(0.3, 0.2, 12.0),
+        ..default()
+    };
+    let energy = |transform: &Transform, velocity: &Velocity| {
+        0.5 * velocity.angular.dot(mass.world_inertia(transform.rotation) * velocity.angular)
+    };
+    let momentum = |transform: &Transform, velocity: &Velocity| {
+        mass.world_inertia(transform.rotation) * velocity.angular
+    };
+    let (start_energy, start_momentum) = (energy(&transform, &velocity), momentum(&transform, &velocity));
+
+    for step in 0..64 * 60 {
+        velocity.integrate(&mut transform, &mass, Vec3::ZERO, Vec3::ZERO, 1.0 / 64.0);
+        // Tumbling end over end, yet neither gaining energy nor bleeding it away
+        let (energy, momentum) = (energy(&transform, &velocity), momentum(&transform, &velocity));
+        assert!((energy - start_energy).abs() < 5e-3 * start_energy, "step {step}: {energy} J");
+        assert!((momentum.length() - start_momentum.length()).abs() < 5e-3 * start_momentum.length());
+    }
+    let drift = momentum(&transform, &velocity).angle_between(start_momentum);
+    assert!(drift < 0.1, "angular momentum turned by {drift} rad");
+}
+
+#[test]
+fn off_centre_points_turn_about_their_centre_of_mass() {
+    // Two litre cubes of water either side of x = 2
+    let points = [Vec3::new(1.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0)];
+    let mass = MassProperties::from_points(1000.0, &points, &[1e-3, 1e-3]);
+    assert!(mass.centre.distance(Vec3::new(2.0, 0.0, 0.0)) < 1e-6, "{}", mass.centre);
+    // A metre out each, not one and three from the origin, plus each cube's own
+    let own = 1e-2 / 6.0;
+    assert!((mass.inertia.y - (2.0 + 2.0 * own)).abs() < 1e-4, "{}", mass.inertia);
+
+    let mut transform = Transform::IDENTITY;
+    let mut velocity = Velocity {
+        angular: Vec3::Y,
+        ..default()
+    };
+    for _ in 0..200 {
+        velocity.integrate(&mut transform, &mass, Vec3::ZERO, Vec3::ZERO, 1.0 / 64.0);
+    }
+    // Spinning in place about the middle, swinging the origin round it
+    let centre = transform.transform_point(mass.centre);
+    assert!(centre.distance(Vec3::new(2.0, 0.0, 0.0)) < 1e-4, "{centre}");
+    assert!(transform.translation.distance(Vec3::new(2.0, 0.0, 0.0)) > 1.9);
+}
+
+#[test]
+fn rider_raises_the_centre_of_gravity() {
+    let board = MassProperties::cuboid(200.0, Vec3::new(2.0, 0.1, 0.5));
+    let ridden = board.with_point_mass(80.0, Vec3::Y);
+    assert_eq!(ridden.mass, 100.0);
+    assert!(ridden.centre.distance(Vec3::new(0.0, 0.8, 0.0)) < 1e-6, "{}", ridden.centre);
+    // The board 0.8 m below the shared centre and the rider 0.2 m above it, both rolling and pitching about it
+    let added = 20.0 * 0.8 * 0.8 + 80.0 * 0.2 * 0.2;
+    let expected = board.inertia + Vec3::new(added, 0.0, added);
+    assert!(ridden.inertia.distance(expected) < 1e-4, "{} != {expected}", ridden.inertia);
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use isosurf::{
+    simd::{SimdF32, SinCosPrecision, f32x16, sin_cos},
+    spectrum::{OceanSpectrum, SpectrumSampling},
+    water::{BasePositions, WaterWaves, create_water_mesh, displace_water_surface_with_precision},
+};
+use wide::{f32x4, f32x8};
+
+/// Largest error of `sin_cos::<S>` against f64 over phases in [-2000, 2000]
+fn max_sin_cos_error<S: SimdF32>(precision: SinCosPrecision) -> f64 {
+    let phases: Vec<f32> = (-400_000..=400_000).map(|i| i as f32 * 0.005).collect();
+    let mut max_error = 0.0f64;
+
+    for chunk in phases.chunks(S::LANES) {
+        let (sin, cos) = sin_cos(S::load(chunk), precision);
+        let (mut sines, mut cosines) = ([0.0; 16], [0.0; 16]);
+        sin.store(&mut sines[..chunk.len()]);
+        cos.store(&mut cosines[..chunk.len()]);
+
+        for (phase, (sin, cos)) in chunk.iter().zip(sines.iter().zip(&cosines)) {
+            let phase = *phase as f64;
+            max_error = max_error.max((*sin as f64 - phase.sin()).abs()).max((*cos as f64 - phase.cos()).abs());
+        }
+    }
+    max_error
+}
+
+#[test]
+fn every_tier_stays_within_its_error() {
+    for precision in SinCosPrecision::ALL {
+        let errors = [
+            max_sin_cos_error::<f32x4>(precision),
+            max_sin_cos_error::<f32x8>(precision),
+            max_sin_cos_error::<f32x16>(precision),
+        ];
+        for error in errors {
+            assert!(error <= precision.max_error() as f64, "{precision:?}: {error}");
+        }
+        assert!(errors.iter().all(|error| *error == errors[0]), "{precision:?} differs between lane widths");
+    }
+
+    // Each approximate tier actually trades accuracy, rather than running the next more exact path
+    assert!(max_sin_cos_error::<f32x8>(SinCosPrecision::Fast) > 1e-6);
+    assert!(max_sin_cos_error::<f32x8>(SinCosPrecision::Fastest) > SinCosPrecision::Fast.max_error() as f64);
+}
+
+#[test]
+fn surface_error_is_bounded_by_the_tier() {
+    let spectrum = WaterWaves::from_spectrum(&OceanSpectrum::default(), &SpectrumSampling::default());
+    let base_positions = BasePositions::new(&create_water_mesh(64, 200.0).1);
+    let count = base_positions.len();
+    let time = 41.3;
+
+    for waves in [WaterWaves::default(), spectrum] {
+        let displace = |precision| {
+            let mut surface = (vec![[0.0; 3]; count], vec![[0.0; 3]; count], vec![[0.0; 4]; count]);
+            displace_water_surface_with_precision(
+                precision,
+                &base_positions,
+                &waves.waves,
+                time,
+                &mut surface.0,
+                &mut surface.1,
+                &mut surface.2,
+            );
+            surface
+        };
+        let (exact_positions, exact_normals, _) = displace(SinCosPrecision::Exact);
+
+        // Each wave moves a vertex by A sin along y and Q A cos along its direction, so errors of at
+        // most e in sin and cos move it by at most e A (1 + Q). Slopes scale the same way with k.
+        let displacement_scale: f32 = waves.waves.iter().map(|wave| wave.amplitude * (1.0 + wave.steepness)).sum();
+        let slope_scale: f32 = waves
+            .waves
+            .iter()
+            .map(|wave| wave.amplitude * wave.wave_number * (1.0 + wave.steepness))
+            .sum();
+
+        for precision in [SinCosPrecision::Fast, SinCosPrecision::Fastest] {
+            let (positions, normals, _) = displace(precision);
+            let position_bound = precision.max_error() * displacement_scale;
+            let normal_bound = 2.0 * precision.max_error() * slope_scale;
+
+            for i in 0..count {
+                let position_error = Vec3::from(positions[i]).distance(Vec3::from(exact_positions[i]));
+                let normal_error = Vec3::from(normals[i]).distance(Vec3::from(exact_normals[i]));
+                let context = format!("{precision:?} [{i}]");
+                assert!(position_error <= position_bound, "{context}: {position_error} > {position_bound}");
+                assert!(normal_error <= normal_bound, "{context}: {normal_error} > {normal_bound}");
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::f32::consts::PI;
+
+use isosurf::{
+    spectrum::{JonswapParameters, OceanSpectrum, SpectrumSampling, sample_spectrum, wave_number_for_frequency},
+    water::GRAVITY,
+};
+
+/// ∫ S(ω) dω between `from` and `to` by the midpoint rule
+fn variance(spectrum: &OceanSpectrum, from: f32, to: f32) -> f32 {
+    let steps = 4000;
+    let step = (to - from) / steps as f32;
+    (0..steps).map(|i| spectrum.density(from + (i as f32 + 0.5) * step) * step).sum()
+}
+
+#[test]
+fn wave_numbers_satisfy_the_dispersion_relation() {
+    for omega in [0.3, 1.0, 2.5] {
+        assert_eq!(wave_number_for_frequency(omega, None), omega * omega / GRAVITY);
+        for depth in [0.5, 5.0, 50.0] {
+            let k = wave_number_for_frequency(omega, Some(depth));
+            let residual = GRAVITY * k * (k * depth).tanh() - omega * omega;
+            assert!(residual.abs() < 1e-4 * omega * omega, "ω {omega} at {depth}m: k {k}");
+        }
+    }
+    // Shallow water waves travel at sqrt(g h), deep ones do not feel the bottom
+    let (omega, shallow) = (0.2, 0.5);
+    let k = wave_number_for_frequency(omega, Some(shallow));
+    assert!((omega / k - (GRAVITY * shallow).sqrt()).abs() < 0.01, "{}", omega / k);
+    let deep = wave_number_for_frequency(2.0, Some(500.0));
+    assert!((deep - wave_number_for_frequency(2.0, None)).abs() < 1e-5);
+}
+
+#[test]
+fn spectra_peak_and_carry_their_closed_form_energy() {
+    let wind_speed = 12.0;
+    let pierson_moskowitz = OceanSpectrum::PiersonMoskowitz { wind_speed };
+    let peak = pierson_moskowitz.peak_frequency();
+    assert!(pierson_moskowitz.density(peak) > pierson_moskowitz.density(0.9 * peak));
+    assert!(pierson_moskowitz.density(peak) > pierson_moskowitz.density(1.1 * peak));
+    // α g² / (5 ω_p⁴), for a significant wave height of about 0.21 U² / g at 19.5m
+    let expected = 0.0081 * GRAVITY * GRAVITY / (5.0 * peak.powi(4));
+    let total = variance(&pierson_moskowitz, 0.01, 20.0);
+    assert!((total - expected).abs() < 1e-2 * expected, "{total} != {expected}");
+    let significant_height = 4.0 * total.sqrt();
+    let expected_height = 0.21 * (1.026 * wind_speed).powi(2) / GRAVITY;
+    assert!((significant_height - expected_height).abs() < 0.2, "{significant_height}");
+
+    // JONSWAP is its gamma = 1 shape lifted by gamma at the peak
+    let jonswap = JonswapParameters::default();
+    let flat = OceanSpectrum::Jonswap(JonswapParameters { gamma: 1.0, ..jonswap });
+    let peaked = OceanSpectrum::Jonswap(jonswap);
+    let peak = peaked.peak_frequency();
+    assert!((peaked.density(peak) / flat.density(peak) - jonswap.gamma).abs() < 1e-4);
+    assert!((peaked.density(3.0 * peak) - flat.density(3.0 * peak)).abs() < 1e-4 * flat.density(3.0 * peak));
+    // A peak period overrides the fetch
+    let swell = JonswapParameters {
+        peak_period: Some(12.0),
+        ..jonswap
+    };
+    assert!((swell.peak_frequency() - 2.0 * PI / 12.0).abs() < 1e-6);
+
+    // TMA loses the long waves in shallow water and matches JONSWAP in deep water
+    let tma = |depth| OceanSpectrum::Tma { jonswap, depth };
+    assert!(tma(2.0).density(peak) < 0.5 * peaked.density(peak));
+    assert!((tma(500.0).density(peak) - peaked.density(peak)).abs() < 1e-6);
+}
+
+#[test]
+fn sampled_waves_carry_the_spectrum_variance() {
+    let spectrum = OceanSpectrum::default();
+    let sampling = SpectrumSampling {
+        component_count: 256,
+        ..Default::default()
+    };
+    let waves = sample_spectrum(&spectrum, &sampling);
+    assert_eq!(waves.len(), 256);
+    assert!(waves.iter().all(|wave| (wave.direction.length() - 1.0).abs() < 1e-5));
+    assert!(waves.iter().all(|wave| (wave.wave_number - wave_number_for_frequency(wave.speed, None)).abs() < 1e-6));
+
+    // A sinusoid of amplitude A has variance A² / 2
+    let peak = spectrum.peak_frequency();
+    let expected = variance(&spectrum, sampling.min_frequency_ratio * peak, sampling.max_frequency_ratio * peak);
+    let sampled: f32 = waves.iter().map(|wave| wave.amplitude * wave.amplitude / 2.0).sum();
+    assert!((sampled - expected).abs() < 0.05 * expected, "{sampled} != {expected}");
+
+    // Reproducible from the seed
+    let again = sample_spectrum(&spectrum, &sampling);
+    assert!(waves.iter().zip(&again).all(|(a, b)| a.phase == b.phase && a.direction == b.direction));
+
+    // No wind, no waves, rather than infinite or NaN ones
+    let calm = [
+        OceanSpectrum::Phillips { wind_speed: 0.0, alpha: 0.0081 },
+        OceanSpectrum::PiersonMoskowitz { wind_speed: 0.0 },
+        OceanSpectrum::Jonswap(JonswapParameters {
+            wind_speed: 0.0,
+            ..Default::default()
+        }),
+    ];
+    for spectrum in calm {
+        assert!(sample_spectrum(&spectrum, &sampling).is_empty(), "{spectrum:?}");
+        assert_eq!(spectrum.density(1.0), 0.0);
+    }
+}
@@ -0,0 +1,76 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use isosurf::spreading::DirectionalSpreading;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const PEAK: f32 = 0.8;
+const WIND_SPEED: f32 = 10.0;
+
+/// ∫ D(θ) dθ between `from` and `to` by the midpoint rule
+fn integral(spreading: &DirectionalSpreading, omega: f32, from: f32, to: f32) -> f32 {
+    let steps = 2000;
+    let step = (to - from) / steps as f32;
+    (0..steps)
+        .map(|i| spreading.weight(from + (i as f32 + 0.5) * step, omega, PEAK, WIND_SPEED) * step)
+        .sum()
+}
+
+#[test]
+fn weights_peak_on_the_mean_direction_and_normalise() {
+    let spreadings = [
+        DirectionalSpreading::Cos2s { s: 4.0 },
+        DirectionalSpreading::Mitsuyasu { s_max: 10.0 },
+        DirectionalSpreading::Hasselmann,
+        DirectionalSpreading::DonelanBanner,
+    ];
+    for spreading in spreadings {
+        for omega in [0.5 * PEAK, PEAK, 3.0 * PEAK] {
+            let weight = |theta| spreading.weight(theta, omega, PEAK, WIND_SPEED);
+            assert_eq!(weight(0.0), 1.0, "{spreading:?}");
+            for theta in [0.3, 1.0, 2.5] {
+                assert!(weight(theta) <= weight(theta / 2.0) && weight(theta) >= 0.0, "{spreading:?}");
+                assert!((weight(theta) - weight(-theta)).abs() < 1e-6, "{spreading:?}");
+            }
+        }
+    }
+
+    // Against the closed-form normalisations: cos^2s(θ/2) integrates to 2√π Γ(s + ½) / Γ(s + 1)
+    let cos_4 = integral(&DirectionalSpreading::Cos2s { s: 2.0 }, PEAK, -PI, PI);
+    assert!((cos_4 - 3.0 * PI / 4.0).abs() < 1e-4, "{cos_4}");
+    // and sech²(βθ) to 2 tanh(βπ) / β, with β = 2.61 (ω/ω_p)^1.3 below the peak
+    let omega = 0.8 * PEAK;
+    let beta = 2.61 * 0.8f32.powf(1.3);
+    let sech = integral(&DirectionalSpreading::DonelanBanner, omega, -PI, PI);
+    assert!((sech - 2.0 * (beta * PI).tanh() / beta).abs() < 1e-4, "{sech}");
+}
+
+#[test]
+fn sampled_directions_follow_the_spreading() {
+    let mean_direction = Vec2::new(0.6, 0.8);
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+    assert_eq!(
+        DirectionalSpreading::None.sample_direction(mean_direction, PEAK, PEAK, WIND_SPEED, &mut rng),
+        mean_direction
+    );
+
+    let spreading = DirectionalSpreading::Cos2s { s: 3.0 };
+    let (samples, bins) = (20_000, 16);
+    let mut histogram = vec![0; bins];
+    for _ in 0..samples {
+        let direction = spreading.sample_direction(mean_direction, PEAK, PEAK, WIND_SPEED, &mut rng);
+        assert!((direction.length() - 1.0).abs() < 1e-5);
+        let theta = mean_direction.angle_to(direction);
+        histogram[(((theta + PI) / (2.0 * PI) * bins as f32) as usize).min(bins - 1)] += 1;
+    }
+
+    // Each bin holds its share of the spreading's integral
+    let total = integral(&spreading, PEAK, -PI, PI);
+    for (bin, &count) in histogram.iter().enumerate() {
+        let from = -PI + 2.0 * PI * bin as f32 / bins as f32;
+        let expected = integral(&spreading, PEAK, from, from + 2.0 * PI / bins as f32) / total;
+        let sampled = count as f32 / samples as f32;
+        assert!((sampled - expected).abs() < 0.01, "bin {bin}: {sampled} != {expected}");
+    }
+}
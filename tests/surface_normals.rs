@@ -0,0 +1,97 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use isosurf::{
+    fft_ocean::FftOcean,
+    water::{
+        BasePositions, WaterDisplacementSettings, WaterSurface, WaterWaves, create_water_mesh,
+        displace_water_surface, displace_water_vertices, update_water_vertices,
+    },
+};
+
+fn displaced(base_positions: &[Vec3], waves: &WaterWaves, time: f32) -> Vec<Vec3> {
+    let mut pos_data = vec![[0.0; 3]; base_positions.len()];
+    displace_water_vertices(&BasePositions::new(base_positions), &waves.waves, time, &mut pos_data);
+    pos_data.into_iter().map(Vec3::from).collect()
+}
+
+#[test]
+fn analytic_normals_match_finite_differences() {
+    let waves = WaterWaves::default();
+    let time = 4.2;
+    // Odd count so the last SIMD chunk is partial
+    let base_positions: Vec<Vec3> = (0..23)
+        .map(|i| Vec3::new(i as f32 * 3.1 - 35.0, 0.0, (i as f32 * 0.7).cos() * 20.0))
+        .collect();
+
+    let count = base_positions.len();
+    let mut pos_data = vec![[0.0; 3]; count];
+    let mut normals = vec![[0.0; 3]; count];
+    let mut tangents = vec![[0.0; 4]; count];
+    let soa = BasePositions::new(&base_positions);
+    displace_water_surface(&soa, &waves.waves, time, &mut pos_data, &mut normals, &mut tangents);
+
+    let h = 1e-2;
+    let offset = |delta: Vec3| base_positions.iter().map(|pos| *pos + delta).collect::<Vec<_>>();
+    let displaced_by = |delta: Vec3| displaced(&offset(delta), &waves, time);
+    let (plus_x, minus_x) = (displaced_by(Vec3::X * h), displaced_by(-Vec3::X * h));
+    let (plus_z, minus_z) = (displaced_by(Vec3::Z * h), displaced_by(-Vec3::Z * h));
+    let positions = displaced(&base_positions, &waves, time);
+
+    for i in 0..count {
+        assert!(Vec3::from(pos_data[i]).distance(positions[i]) < 1e-4);
+
+        let tangent_x = (plus_x[i] - minus_x[i]) / (2.0 * h);
+        let tangent_z = (plus_z[i] - minus_z[i]) / (2.0 * h);
+        let expected_normal = tangent_z.cross(tangent_x).normalize();
+        let expected_tangent = tangent_x.normalize();
+
+        let normal = Vec3::from(normals[i]);
+        let tangent = Vec3::from_slice(&tangents[i]);
+        assert!(normal.distance(expected_normal) < 2e-3, "{i}: {normal} != {expected_normal}");
+        assert!(tangent.distance(expected_tangent) < 2e-3, "{i}: {tangent} != {expected_tangent}");
+        assert!(normal.is_normalized() && tangent.is_normalized());
+
+        // Bitangent follows +z, the direction the v texture coordinate grows in
+        let bitangent = normal.cross(tangent) * tangents[i][3];
+        assert!(bitangent.dot(tangent_z) > 0.0);
+    }
+}
+
+#[test]
+fn fft_slopes_tilt_normals_and_tangents_together() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_resource::<WaterDisplacementSettings>()
+        .add_systems(Update, update_water_vertices);
+    let (grid_size, world_size) = (40, 50.0);
+    let (mesh, base_positions) = create_water_mesh(grid_size, world_size);
+    let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(mesh);
+    let surface = WaterSurface {
+        grid_size,
+        world_size,
+        vertex_count: base_positions.len(),
+        base_positions: BasePositions::new(&base_positions),
+        seams: Vec::new(),
+    };
+    app.world_mut().spawn((Mesh3d(mesh.clone()), surface, FftOcean::default()));
+    app.update();
+
+    let world = app.world_mut();
+    let mut oceans = world.query::<&FftOcean>();
+    let world = &*world;
+    let ocean = oceans.single(world).unwrap();
+    let mesh = world.resource::<Assets<Mesh>>().get(&mesh).unwrap();
+    let (Some(VertexAttributeValues::Float32x3(normals)), Some(VertexAttributeValues::Float32x4(tangents))) =
+        (mesh.attribute(Mesh::ATTRIBUTE_NORMAL), mesh.attribute(Mesh::ATTRIBUTE_TANGENT))
+    else {
+        panic!("water mesh has no normals or tangents");
+    };
+    for ((normal, tangent), base) in normals.iter().zip(tangents).zip(&base_positions) {
+        let (normal, tangent) = (Vec3::from(*normal), Vec3::from_slice(tangent));
+        // Tilted by the FFT slope, with the tangent still in the surface
+        let gradient = ocean.gradient_at(Vec2::new(base.x, base.z));
+        let expected = Vec3::new(-gradient.x, 1.0, -gradient.y).normalize();
+        assert!(normal.distance(expected) < 1e-4, "{base}: {normal} != {expected}");
+        assert!(tangent.is_normalized() && tangent.dot(normal).abs() < 1e-4, "{base}: {tangent}");
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::{
+    prelude::*,
+    render::mesh::{MeshVertexAttribute, VertexAttributeValues},
+};
+use isosurf::{
+    board_presets::BoardPreset,
+    hull_buoyancy::BuoyancyHull,
+    surfboard::{Surfboard, create_surfboard_mesh},
+    surfboard_shape::TailShape,
+};
+
+fn vectors(mesh: &Mesh, attribute: MeshVertexAttribute) -> Vec<Vec3> {
+    let Some(VertexAttributeValues::Float32x3(values)) = mesh.attribute(attribute) else {
+        panic!("{} is not Float32x3", attribute.name);
+    };
+    values.iter().map(|&value| Vec3::from(value)).collect()
+}
+
+fn positions(surfboard: &Surfboard) -> Vec<Vec3> {
+    vectors(&create_surfboard_mesh(surfboard), Mesh::ATTRIBUTE_POSITION)
+}
+
+#[test]
+fn shaped_mesh_is_closed_smooth_and_uv_mapped() {
+    let surfboard = Surfboard::default();
+    let mesh = create_surfboard_mesh(&surfboard);
+    let positions = vectors(&mesh, Mesh::ATTRIBUTE_POSITION);
+    let normals = vectors(&mesh, Mesh::ATTRIBUTE_NORMAL);
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        panic!("UVs are not Float32x2");
+    };
+    assert_eq!(positions.len(), normals.len());
+    assert_eq!(positions.len(), uvs.len());
+    assert!(normals.iter().all(|normal| (normal.length() - 1.0).abs() < 1e-4));
+    assert!(uvs.iter().flatten().all(|&uv| (-1e-4..=1.0001).contains(&uv)));
+
+    // The measurements it was shaped to, and a volume the mesh actually encloses
+    let min = positions.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+    let max = positions.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max);
+    assert!(((max - min).x - surfboard.length).abs() < 1e-3);
+    assert!(((max - min).z - surfboard.width).abs() < 1e-3);
+    assert!((max - min).y > surfboard.thickness);
+    let hull = BuoyancyHull::from_mesh(&mesh).unwrap();
+    let slab = surfboard.length * surfboard.width * surfboard.thickness;
+    assert!((hull.volume() - surfboard.volume()).abs() < 1e-6);
+    assert!((surfboard.volume_litres() - surfboard.volume() * 1000.0).abs() < 1e-3);
+    assert!((0.4 * slab..slab).contains(&surfboard.volume()), "{} litres", surfboard.volume_litres());
+
+    // Vertex normals agree with the outward faces around them
+    for &[a, b, c] in &hull.triangles {
+        let [a, b, c] = [a, b, c].map(|vertex| vertex as usize);
+        let face = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+        if face.length() > 1e-6 {
+            assert!((normals[a] + normals[b] + normals[c]).dot(face) > 0.0);
+        }
+    }
+    // Deck up, bottom down and rails out at the middle of the board
+    let middle = |direction: Vec3| {
+        let index = (0..positions.len())
+            .filter(|&i| positions[i].x.abs() < 0.1)
+            .max_by(|&i, &j| positions[i].dot(direction).total_cmp(&positions[j].dot(direction)))
+            .unwrap();
+        normals[index]
+    };
+    for direction in [Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z] {
+        assert!(middle(direction).dot(direction) > 0.95, "{direction}: {}", middle(direction));
+    }
+}
+
+#[test]
+fn shaping_parameters_change_the_board() {
+    let shaped = |tail_shape| Surfboard {
+        tail_shape,
+        ..default()
+    };
+    // Rounded corners and a notch take foam off the block
+    let square = shaped(TailShape::Square);
+    for tail_shape in [TailShape::Squash, TailShape::Swallow] {
+        assert!(shaped(tail_shape).volume() < square.volume(), "{tail_shape:?}");
+    }
+
+    // Width across the last 5 cm of the tail
+    let tail_width = |surfboard: &Surfboard| {
+        let positions = positions(surfboard);
+        let tail = positions.iter().map(|position| position.x).fold(f32::INFINITY, f32::min);
+        let across = positions.iter().filter(|position| position.x < tail + 0.05);
+        2.0 * across.map(|position| position.z.abs()).fold(0.0, f32::max)
+    };
+    assert!(tail_width(&shaped(TailShape::Pin)) < 0.5 * tail_width(&square));
+
+    // A swallow's notch leaves the stringer short of the two points
+    let swallow = positions(&shaped(TailShape::Swallow));
+    let tail = swallow.iter().map(|position| position.x).fold(f32::INFINITY, f32::min);
+    let stringer = swallow.iter().filter(|position| position.z.abs() < 1e-3);
+    assert!(stringer.map(|position| position.x).fold(f32::INFINITY, f32::min) > tail + 0.05);
+
+    // Rocker lifts the nose off a flat bottom
+    let nose_lift = |surfboard: &Surfboard| {
+        let positions = positions(surfboard);
+        let bottom = positions.iter().map(|position| position.y).fold(f32::INFINITY, f32::min);
+        let nose = positions.iter().copied().max_by(|a, b| a.x.total_cmp(&b.x)).unwrap();
+        nose.y - bottom
+    };
+    let flat = Surfboard {
+        nose_rocker: 0.0,
+        ..default()
+    };
+    let rockered = Surfboard {
+        nose_rocker: 0.2,
+        ..default()
+    };
+    assert!(nose_lift(&rockered) > nose_lift(&flat) + 0.15);
+
+    // Thicker and wider boards float more
+    let bigger = Surfboard {
+        width: 0.7,
+        thickness: 0.12,
+        ..default()
+    };
+    assert!(bigger.volume_litres() > Surfboard::default().volume_litres() * 1.3);
+}
+
+/// Times the closed `hull` winds around `point`: about 1 inside it and 0 outside
+fn winding_number(hull: &BuoyancyHull, point: Vec3) -> f32 {
+    let solid_angle: f32 = hull
+        .triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|vertex| hull.vertices[vertex as usize] - point);
+            let (la, lb, lc) = (a.length(), b.length(), c.length());
+            let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+            2.0 * a.dot(b.cross(c)).atan2(denominator)
+        })
+        .sum();
+    solid_angle / (4.0 * std::f32::consts::PI)
+}
+
+#[test]
+fn every_face_has_area_and_faces_out() {
+    // The presets cover every tail shape, and a swallow's notch on both a surfboard and a bodyboard
+    for preset in BoardPreset::ALL {
+        let mesh = create_surfboard_mesh(&preset.surfboard());
+        let normals = vectors(&mesh, Mesh::ATTRIBUTE_NORMAL);
+        let hull = BuoyancyHull::from_mesh(&mesh).unwrap();
+
+        for &[a, b, c] in &hull.triangles {
+            let [pa, pb, pc] = [a, b, c].map(|vertex| hull.vertices[vertex as usize]);
+            let face = (pb - pa).cross(pc - pa);
+            let longest = [pb - pa, pc - pb, pa - pc].map(Vec3::length_squared).into_iter().fold(0.0, f32::max);
+            assert!(face.length() > 1e-3 * longest, "{preset:?}: sliver {pa} {pb} {pc}");
+
+            // Just off the face along its winding normal is outside the board, just behind it inside. Steps stay
+            // well inside the face's own size, as faces at the tips meet others at sharp edges.
+            let (centroid, step) = ((pa + pb + pc) / 3.0, face.normalize() * 1e-2 * face.length().sqrt());
+            assert!(winding_number(&hull, centroid + step) < 0.5, "{preset:?}: inward face at {centroid}");
+            assert!(winding_number(&hull, centroid - step) > 0.5, "{preset:?}: inward face at {centroid}");
+            let shading = [a, b, c].map(|vertex| normals[vertex as usize]).into_iter().sum::<Vec3>();
+            assert!(shading.dot(face) > 0.0, "{preset:?}: normals against the face at {centroid}");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use isosurf::{
+    water::{HeightQuerySettings, WaterBounds, WaterWaves},
+    water_query::WaterQuery,
+};
+
+const POOL_CENTER: Vec3 = Vec3::new(20.0, 1.0, 0.0);
+
+fn spawn_water(app: &mut App, transform: Transform, waves: WaterWaves, bounds: Option<WaterBounds>) {
+    let mut entity = app
+        .world_mut()
+        .spawn((transform, GlobalTransform::from(transform), waves));
+    if let Some(bounds) = bounds {
+        entity.insert(bounds);
+    }
+}
+
+/// An open ocean with a calm, raised pool sitting inside it
+fn ocean_with_pool() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).init_resource::<HeightQuerySettings>();
+    spawn_water(&mut app, Transform::IDENTITY, WaterWaves::default(), None);
+    spawn_water(
+        &mut app,
+        Transform::from_translation(POOL_CENTER),
+        WaterWaves { waves: Vec::new() },
+        Some(WaterBounds::new(Vec2::splat(5.0)).with_blend_distance(1.0)),
+    );
+    app.update();
+    app
+}
+
+fn heights(app: &mut App, points: Vec<Vec3>) -> Vec<Option<f32>> {
+    app.world_mut()
+        .run_system_once(move |water: WaterQuery| points.iter().map(|point| water.height(*point)).collect())
+        .unwrap()
+}
+
+#[test]
+fn pool_covers_ocean() {
+    let mut app = ocean_with_pool();
+    let ocean_point = Vec3::new(-30.0, 0.0, 12.0);
+    let heights = heights(&mut app, vec![POOL_CENTER, ocean_point, POOL_CENTER + Vec3::new(4.5, 0.0, 0.0)]);
+
+    // Inside the pool the calm water sits at its own level
+    assert!((heights[0].unwrap() - 1.0).abs() < 1e-5);
+
+    // Half way through the blend band the pool and ocean contribute equally
+    let ocean_at_band = app
+        .world_mut()
+        .run_system_once(|water: WaterQuery| {
+            let point = POOL_CENTER + Vec3::new(4.5, 0.0, 0.0);
+            let ocean = water.bodies().find(|body| body.bounds.is_none()).unwrap();
+            ocean.sample(point, water.elapsed_secs(), &HeightQuerySettings::default()).position.y
+        })
+        .unwrap();
+    let expected = 0.5 * 1.0 + 0.5 * ocean_at_band;
+    assert!((heights[2].unwrap() - expected).abs() < 1e-4, "{:?} != {expected}", heights[2]);
+
+    // Far away only the ocean is left
+    assert!(heights[1].unwrap().abs() < 3.0);
+}
+
+#[test]
+fn no_water_outside_bounded_bodies() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).init_resource::<HeightQuerySettings>();
+    spawn_water(
+        &mut app,
+        Transform::from_translation(POOL_CENTER),
+        WaterWaves::default(),
+        Some(WaterBounds::new(Vec2::new(5.0, 3.0))),
+    );
+    app.update();
+
+    let points = vec![POOL_CENTER, POOL_CENTER + Vec3::new(0.0, 0.0, 3.5)];
+    let single = heights(&mut app, points.clone());
+    assert!(single[0].is_some());
+    assert!(single[1].is_none());
+
+    let batched = app
+        .world_mut()
+        .run_system_once(move |mut water: WaterQuery| {
+            let mut heights = vec![0.0; points.len()];
+            assert!(water.heights(&points, &mut heights));
+            heights
+        })
+        .unwrap();
+    assert!((batched[0] - single[0].unwrap()).abs() < 1e-3);
+    assert_eq!(batched[1], f32::NEG_INFINITY);
+}
+
+#[test]
+fn batched_heights_blend_like_single_queries() {
+    let mut app = ocean_with_pool();
+    let points: Vec<Vec3> = (0..40)
+        .map(|i| Vec3::new(i as f32 * 0.75, 0.0, (i as f32 * 0.4).sin() * 6.0))
+        .collect();
+    let single = heights(&mut app, points.clone());
+
+    let batched = app
+        .world_mut()
+        .run_system_once(move |mut water: WaterQuery| {
+            let mut heights = vec![0.0; points.len()];
+            assert!(water.heights(&points, &mut heights));
+            heights
+        })
+        .unwrap();
+
+    for (batched, single) in batched.iter().zip(&single) {
+        assert!((batched - single.unwrap()).abs() < 1e-3, "{batched} != {single:?}");
+    }
+}
+
+#[test]
+fn batched_velocities_blend_like_single_queries() {
+    let mut app = ocean_with_pool();
+    // Down through the pool's blend band and into the deep
+    let points: Vec<Vec3> = (0..40)
+        .map(|i| Vec3::new(14.0 + i as f32 * 0.3, -(i as f32) * 0.25, (i as f32 * 0.4).sin() * 2.0))
+        .collect();
+
+    let (single, batched) = app
+        .world_mut()
+        .run_system_once(move |mut water: WaterQuery| {
+            let single: Vec<Vec3> = points.iter().map(|point| water.velocity_at_depth(*point).unwrap()).collect();
+            let mut batched = vec![Vec3::NAN; points.len()];
+            assert!(water.velocities_at_depth(&points, &mut batched));
+            (single, batched)
+        })
+        .unwrap();
+
+    assert!(single.iter().any(|velocity| velocity.length() > 0.1));
+    for (batched, single) in batched.iter().zip(&single) {
+        assert!(batched.distance(*single) < 1e-4, "{batched} != {single}");
+    }
+}
+
+#[test]
+fn dominant_body_resolves_by_priority() {
+    let mut app = ocean_with_pool();
+
+    let (in_pool, in_ocean) = app
+        .world_mut()
+        .run_system_once(|water: WaterQuery| {
+            let in_pool = water.body_at(POOL_CENTER).unwrap().bounds.is_some();
+            let in_ocean = water.body_at(Vec3::new(-40.0, 0.0, 0.0)).unwrap().bounds.is_none();
+            (in_pool, in_ocean)
+        })
+        .unwrap();
+    assert!(in_pool && in_ocean);
+}
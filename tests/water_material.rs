@@ -0,0 +1,133 @@
+use bevy::{
+    ecs::system::RunSystemOnce,
+    prelude::*,
+    render::{primitives::Aabb, render_resource::ShaderType},
+};
+use isosurf::{
+    water::{BasePositions, WaterWaves, displace_water_surface, get_wave_displacement},
+    water_material::{FlatWaterBounds, GpuWave, GpuWaves, MAX_GPU_WAVES, WaterMaterial, update_water_material},
+};
+
+const WATER_WAVES_WGSL: &str = include_str!("../src/shaders/water_waves.wgsl");
+
+/// naga does not know the naga_oil import directives Bevy's shader preprocessor consumes
+fn parse_water_waves_shader() -> naga::Module {
+    let source: String = WATER_WAVES_WGSL
+        .lines()
+        .filter(|line| !line.starts_with("#define_import_path"))
+        .map(|line| format!("{line}\n"))
+        .collect();
+
+    naga::front::wgsl::parse_str(&source).unwrap_or_else(|error| panic!("{}", error.emit_to_string(&source)))
+}
+
+#[test]
+fn water_waves_shader_validates() {
+    let module = parse_water_waves_shader();
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+        .validate(&module)
+        .unwrap_or_else(|error| panic!("{}", error.emit_to_string(WATER_WAVES_WGSL)));
+
+    // The uniform layout in WGSL has to match what encase writes for the Rust types
+    let mut layouter = naga::proc::Layouter::default();
+    layouter.update(module.to_ctx()).unwrap();
+    let size_of = |name: &str| {
+        let (handle, _) = module.types.iter().find(|(_, ty)| ty.name.as_deref() == Some(name)).unwrap();
+        layouter[handle].size as u64
+    };
+    assert_eq!(size_of("Wave"), GpuWave::min_size().get());
+    assert_eq!(size_of("WaterWaves"), GpuWaves::min_size().get());
+
+    let max_waves = module.constants.iter().find(|(_, constant)| constant.name.as_deref() == Some("MAX_WAVES"));
+    assert!(max_waves.is_some());
+    assert!(WATER_WAVES_WGSL.contains(&format!("const MAX_WAVES: u32 = {MAX_GPU_WAVES}u;")));
+}
+
+/// Line-by-line port of `add_gerstner_wave` and `water_surface` in water_waves.wgsl
+fn shader_water_surface(grid_position: Vec2, water_waves: &GpuWaves) -> (Vec3, Vec3) {
+    let (mut displacement, mut tangent_x, mut tangent_z) = (Vec3::ZERO, Vec3::X, Vec3::Z);
+    let wave_count = (water_waves.wave_count as usize).min(MAX_GPU_WAVES);
+
+    for wave in &water_waves.waves[..wave_count] {
+        let phase =
+            wave.wave_number * grid_position.dot(wave.direction) - (wave.speed * water_waves.time - wave.phase);
+        let sin_phase = phase.sin();
+        let cos_phase = phase.cos();
+        let direction = Vec3::new(wave.direction.x, 0.0, wave.direction.y);
+        let q_a = wave.steepness * wave.amplitude;
+
+        let q_a_k_sin = q_a * wave.wave_number * sin_phase;
+        let a_k_cos = wave.amplitude * wave.wave_number * cos_phase;
+
+        displacement += direction * (q_a * cos_phase) + Vec3::new(0.0, wave.amplitude * sin_phase, 0.0);
+        tangent_x += Vec3::new(0.0, a_k_cos, 0.0) * wave.direction.x - direction * (q_a_k_sin * wave.direction.x);
+        tangent_z += Vec3::new(0.0, a_k_cos, 0.0) * wave.direction.y - direction * (q_a_k_sin * wave.direction.y);
+    }
+
+    (displacement, tangent_z.cross(tangent_x).normalize())
+}
+
+#[test]
+fn shader_math_matches_cpu_displacement() {
+    let waves = WaterWaves::default();
+    let time = 7.3;
+    let gpu_waves = GpuWaves::new(&waves.waves, time);
+    assert_eq!(gpu_waves.wave_count as usize, waves.waves.len());
+
+    let base_positions: Vec<Vec3> = (0..17)
+        .map(|i| Vec3::new(i as f32 * 5.3 - 40.0, 0.0, (i as f32 * 1.3).sin() * 30.0))
+        .collect();
+    let count = base_positions.len();
+    let mut pos_data = vec![[0.0; 3]; count];
+    let mut normals = vec![[0.0; 3]; count];
+    let mut tangents = vec![[0.0; 4]; count];
+    let soa = BasePositions::new(&base_positions);
+    displace_water_surface(&soa, &waves.waves, time, &mut pos_data, &mut normals, &mut tangents);
+
+    for (i, base_pos) in base_positions.iter().enumerate() {
+        let grid_position = base_pos.xz();
+        let (displacement, normal) = shader_water_surface(grid_position, &gpu_waves);
+
+        let expected = get_wave_displacement(grid_position, &waves.waves, time);
+        assert!(displacement.distance(expected) < 1e-5, "{displacement} != {expected}");
+        assert!(normal.distance(Vec3::from(normals[i])) < 1e-4, "{normal} != {:?}", normals[i]);
+    }
+}
+
+#[test]
+fn extra_waves_are_dropped_from_the_uniform() {
+    let waves = vec![WaterWaves::default().waves[0]; MAX_GPU_WAVES + 5];
+    let gpu_waves = GpuWaves::new(&waves, 1.0);
+    assert_eq!(gpu_waves.wave_count as usize, MAX_GPU_WAVES);
+    assert_eq!(gpu_waves.waves[MAX_GPU_WAVES - 1], GpuWave::from(&waves[0]));
+}
+
+#[test]
+fn culling_bounds_follow_the_waves() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default())).init_asset::<WaterMaterial>();
+    let material = app.world_mut().resource_mut::<Assets<WaterMaterial>>().add(WaterMaterial::default());
+
+    let flat = FlatWaterBounds(Aabb::from_min_max(Vec3::new(-50.0, 0.0, -50.0), Vec3::new(50.0, 0.0, 50.0)));
+    let water = app.world_mut().spawn((MeshMaterial3d(material), flat, Aabb::default(), WaterWaves::default()));
+    let water = water.id();
+
+    // Swap in a much larger spectrum after spawning, as a game changing the sea state would
+    let mut waves = WaterWaves::default();
+    for wave in &mut waves.waves {
+        wave.amplitude *= 4.0;
+    }
+    let gpu_waves = GpuWaves::new(&waves.waves, 3.1);
+    app.world_mut().entity_mut(water).insert(waves);
+    app.world_mut().run_system_once(update_water_material).unwrap();
+
+    let aabb = *app.world().get::<Aabb>(water).unwrap();
+    for i in 0..=20 {
+        for j in 0..=20 {
+            let grid_position = Vec2::new(i as f32 * 5.0 - 50.0, j as f32 * 5.0 - 50.0);
+            let (displacement, _) = shader_water_surface(grid_position, &gpu_waves);
+            let offset = Vec3A::from(grid_position.extend(0.0).xzy() + displacement) - aabb.center;
+            assert!(offset.abs().cmple(aabb.half_extents).all(), "{offset} outside {:?}", aabb.half_extents);
+        }
+    }
+}
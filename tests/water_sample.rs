@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use isosurf::water::{
+    HeightQuerySettings, WaterWaves, get_displaced_wave_height, sample_gerstner_surface, sample_water,
+    sample_water_velocity_at_depth,
+};
+
+const EPSILON: f32 = 1e-2;
+
+#[test]
+fn velocity_and_acceleration_match_time_derivatives() {
+    let waves = WaterWaves::default();
+    let grid_position = Vec2::new(7.0, -3.0);
+    let (time, dt) = (3.0, 1e-2);
+
+    let before = sample_gerstner_surface(grid_position, &waves.waves, time - dt);
+    let now = sample_gerstner_surface(grid_position, &waves.waves, time);
+    let after = sample_gerstner_surface(grid_position, &waves.waves, time + dt);
+
+    let velocity = (after.position - before.position) / (2.0 * dt);
+    let acceleration = (after.velocity - before.velocity) / (2.0 * dt);
+    assert!(now.velocity.distance(velocity) < EPSILON, "{} != {velocity}", now.velocity);
+    assert!(now.acceleration.distance(acceleration) < EPSILON, "{} != {acceleration}", now.acceleration);
+}
+
+#[test]
+fn gradient_matches_displaced_height_slope() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    let (position, time, h) = (Vec2::new(-12.0, 5.5), 8.0, 1e-2);
+
+    let sample = sample_water(position, &waves.waves, time, &settings);
+    let height = |offset: Vec2| get_displaced_wave_height(position + offset, &waves.waves, time, &settings);
+    let slope = Vec2::new(
+        (height(Vec2::X * h) - height(-Vec2::X * h)) / (2.0 * h),
+        (height(Vec2::Y * h) - height(-Vec2::Y * h)) / (2.0 * h),
+    );
+
+    assert!(sample.gradient.distance(slope) < EPSILON, "{} != {slope}", sample.gradient);
+    assert!((sample.normal.length() - 1.0).abs() < 1e-5);
+    assert!(Vec2::new(sample.position.x, sample.position.z).distance(position) < 1e-3);
+}
+
+#[test]
+fn subsurface_velocity_matches_surface_and_decays() {
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    let (position, time) = (Vec2::new(2.0, 9.0), 1.5);
+
+    let surface = sample_water(position, &waves.waves, time, &settings).velocity;
+    let at_surface = sample_water_velocity_at_depth(position, 0.0, &waves.waves, time, &settings);
+    assert!(surface.distance(at_surface) < 1e-4, "{surface} != {at_surface}");
+
+    let deep = sample_water_velocity_at_depth(position, 50.0, &waves.waves, time, &settings);
+    assert!(deep.length() < 0.01 * surface.length().max(0.1));
+}
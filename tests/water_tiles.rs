@@ -0,0 +1,187 @@
+use bevy::{
+    ecs::system::RunSystemOnce,
+    prelude::*,
+    render::{camera::CameraProjection, mesh::VertexAttributeValues},
+};
+use isosurf::{
+    clipmap::stitch_seams,
+    water::{
+        BasePositions, WaterDisplacementSettings, WaterSurface, WaterWaves, displace_water_surface,
+        update_water_vertices,
+    },
+    water_tiles::{WaterTile, WaterTiles, create_tile_mesh, spawn_water_tiles, update_water_tiles},
+};
+
+#[test]
+fn neighbouring_lods_differ_by_at_most_one() {
+    let tiles = WaterTiles {
+        tile_count: 12,
+        lod_distance: 10.0,
+        ..default()
+    };
+
+    for focus in [Vec3::new(0.0, 5.0, 0.0), Vec3::new(-140.0, 2.0, 130.0), Vec3::new(30.0, 200.0, -10.0)] {
+        let lods = tiles.select_lods(focus);
+        assert!(lods.iter().all(|lod| *lod < tiles.lod_count));
+
+        for (coord, lod) in tiles.coords().zip(&lods) {
+            for neighbour in tiles.neighbour_lods(coord, &lods).into_iter().flatten() {
+                assert!(lod.abs_diff(neighbour) <= 1, "{coord}: {lod} next to {neighbour}");
+            }
+        }
+    }
+
+    // Close to the water the tile underneath is always at full resolution, far ones are coarser
+    let lods = tiles.select_lods(Vec3::new(0.0, 5.0, 0.0));
+    let under_focus = tiles.coords().position(|coord| tiles.tile_rect(coord).contains(Vec2::ZERO)).unwrap();
+    assert_eq!(lods[under_focus], 0);
+    assert_eq!(lods[0], tiles.lod_count - 1);
+}
+
+#[test]
+fn borders_between_lods_do_not_crack() {
+    let tiles = WaterTiles::default();
+    let waves = WaterWaves::default().waves;
+    let (fine_coord, coarse_coord) = (IVec2::new(3, 3), IVec2::new(4, 3));
+
+    let displaced = |coord: IVec2, lod: usize| {
+        let (_, base_positions) = create_tile_mesh(&tiles, coord, lod);
+        let mut pos_data = vec![[0.0; 3]; base_positions.len()];
+        let mut normals = vec![[0.0; 3]; base_positions.len()];
+        let mut tangents = vec![[0.0; 4]; base_positions.len()];
+        let base_positions = BasePositions::new(&base_positions);
+        displace_water_surface(&base_positions, &waves, 2.5, &mut pos_data, &mut normals, &mut tangents);
+        (pos_data, normals, tangents)
+    };
+
+    let (mut fine, mut fine_normals, mut fine_tangents) = displaced(fine_coord, 0);
+    let seams = tiles.seam_vertices(0, [None, Some(1), None, None]);
+    stitch_seams(&seams, &mut fine, &mut fine_normals, &mut fine_tangents);
+    let (coarse, coarse_normals, _) = displaced(coarse_coord, 1);
+
+    let fine_grid = tiles.resolution(0) + 1;
+    let coarse_grid = tiles.resolution(1) + 1;
+    for z in 0..fine_grid {
+        let fine_vertex = Vec3::from(fine[z * fine_grid + fine_grid - 1]);
+        let coarse_below = Vec3::from(coarse[(z / 2) * coarse_grid]);
+        let normal_below = Vec3::from(coarse_normals[(z / 2) * coarse_grid]);
+        let (expected, expected_normal) = if z % 2 == 0 {
+            (coarse_below, normal_below)
+        } else {
+            let above = (z / 2 + 1) * coarse_grid;
+            let normal = (normal_below + Vec3::from(coarse_normals[above])).normalize();
+            (coarse_below.midpoint(Vec3::from(coarse[above])), normal)
+        };
+        assert!(fine_vertex.distance(expected) < 1e-4, "row {z}: {fine_vertex} != {expected}");
+        let fine_normal = Vec3::from(fine_normals[z * fine_grid + fine_grid - 1]);
+        assert!(fine_normal.distance(expected_normal) < 1e-4, "row {z}: {fine_normal} != {expected_normal}");
+    }
+}
+
+#[test]
+fn tiles_outside_the_frustum_are_not_displaced() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_resource::<WaterDisplacementSettings>()
+        .add_systems(Update, (update_water_tiles, update_water_vertices).chain());
+
+    let tiles = WaterTiles {
+        lod_distance: 20.0,
+        ..default()
+    };
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>| {
+            spawn_water_tiles(&mut commands, &mut meshes, Handle::default(), Transform::IDENTITY, &tiles);
+        })
+        .unwrap();
+
+    // Hovering over the middle of the field, looking along +x
+    let camera = Transform::from_xyz(0.0, 10.0, 0.0).looking_at(Vec3::new(100.0, 0.0, 0.0), Vec3::Y);
+    let camera = GlobalTransform::from(camera);
+    let frustum = PerspectiveProjection::default().compute_frustum(&camera);
+    app.world_mut().spawn((Camera::default(), camera, frustum));
+    app.update();
+
+    let world = app.world_mut();
+    let mut tiles = world.query::<(&WaterTile, &WaterSurface, &Mesh3d)>();
+    let meshes = world.resource::<Assets<Mesh>>();
+    let mut checked = [false; 2];
+
+    for (tile, surface, mesh_3d) in tiles.iter(world) {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            meshes.get(&mesh_3d.0).unwrap().attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("tile mesh has no positions");
+        };
+        assert_eq!(positions.len(), surface.base_positions.len());
+        let displaced = positions.iter().any(|position| position[1] != 0.0);
+
+        if tile.coord == IVec2::new(0, 3) {
+            // Behind the camera, and far enough away to have dropped a LOD
+            assert!(!tile.visible && !displaced);
+            assert!(tile.lod > 0);
+            checked[0] = true;
+        }
+        if tile.coord == IVec2::new(5, 3) {
+            assert!(tile.visible && displaced);
+            assert_eq!(tile.lod, 0);
+            checked[1] = true;
+        }
+    }
+    assert_eq!(checked, [true; 2]);
+}
+
+#[test]
+fn tiles_keep_their_lod_until_their_mesh_is_rebuilt() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_resource::<WaterDisplacementSettings>()
+        .add_systems(Update, (update_water_tiles, update_water_vertices).chain());
+
+    let tiles = WaterTiles {
+        lod_distance: 20.0,
+        ..default()
+    };
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>| {
+            spawn_water_tiles(&mut commands, &mut meshes, Handle::default(), Transform::IDENTITY, &tiles);
+        })
+        .unwrap();
+    let camera = GlobalTransform::from(Transform::from_xyz(0.0, 10.0, 0.0));
+    let frustum = PerspectiveProjection::default().compute_frustum(&camera);
+    app.world_mut().spawn((Camera::default(), camera, frustum));
+
+    // Lose the mesh of a corner tile far enough away to drop a LOD
+    let world = app.world_mut();
+    let (corner, mesh) = world
+        .query::<(Entity, &WaterTile, &Mesh3d)>()
+        .iter(world)
+        .find(|(_, tile, _)| tile.coord == IVec2::ZERO)
+        .map(|(entity, _, mesh_3d)| (entity, mesh_3d.0.id()))
+        .unwrap();
+    let lost = world.resource_mut::<Assets<Mesh>>().remove(mesh).unwrap();
+    app.update();
+
+    let tile = app.world().get::<WaterTile>(corner).unwrap();
+    let surface = app.world().get::<WaterSurface>(corner).unwrap();
+    assert_eq!(tile.lod, 0);
+    assert!(surface.seams.iter().flatten().all(|&index| (index as usize) < surface.vertex_count));
+
+    // Once it is back the tile catches up
+    app.world_mut().resource_mut::<Assets<Mesh>>().insert(mesh, lost);
+    app.update();
+    assert!(app.world().get::<WaterTile>(corner).unwrap().lod > 0);
+
+    // From then on nothing moves, so no tile is flagged as changed
+    let last_changed = |app: &mut App| {
+        let world = app.world_mut();
+        let mut tiles = world.query::<Ref<WaterTile>>();
+        tiles.iter(world).map(|tile| tile.last_changed()).collect::<Vec<_>>()
+    };
+    app.update();
+    let settled = last_changed(&mut app);
+    app.update();
+    assert_eq!(last_changed(&mut app), settled);
+}
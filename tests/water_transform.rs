@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*, time::TimeUpdateStrategy};
+use isosurf::{
+    surfboard::{FloatingBody, Surfboard, update_surfboard_physics},
+    water::{HeightQuerySettings, WaterWaves, get_displaced_wave_height},
+    water_query::WaterQuery,
+};
+
+fn app_with_water(transform: Transform) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<HeightQuerySettings>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(16)));
+    app.world_mut()
+        .spawn((transform, GlobalTransform::from(transform), WaterWaves::default()));
+    app.update();
+    app
+}
+
+#[test]
+fn raised_lake_heights_are_offset() {
+    let mut app = app_with_water(Transform::from_xyz(0.0, 50.0, 0.0));
+    let points = [Vec3::new(3.0, 0.0, 4.0), Vec3::new(-20.0, 80.0, 7.5)];
+
+    let (time, heights) = app
+        .world_mut()
+        .run_system_once(move |water: WaterQuery| {
+            (water.elapsed_secs(), points.map(|point| water.height(point).unwrap()))
+        })
+        .unwrap();
+
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    for (point, height) in points.iter().zip(heights) {
+        let local = get_displaced_wave_height(Vec2::new(point.x, point.z), &waves.waves, time, &settings);
+        assert!((height - (50.0 + local)).abs() < 1e-3, "{height} != 50 + {local}");
+    }
+}
+
+#[test]
+fn rotated_tank_samples_in_local_space() {
+    let transform = Transform::from_xyz(10.0, 2.0, -5.0).with_rotation(Quat::from_rotation_y(1.2));
+    let mut app = app_with_water(transform);
+    let point = Vec3::new(14.0, 0.0, 3.0);
+
+    let (time, height, still_level) = app
+        .world_mut()
+        .run_system_once(move |water: WaterQuery| {
+            (water.elapsed_secs(), water.height(point).unwrap(), water.still_water_level(point).unwrap())
+        })
+        .unwrap();
+
+    let local = transform.compute_affine().inverse().transform_point3(point);
+    let waves = WaterWaves::default();
+    let settings = HeightQuerySettings::default();
+    let expected = 2.0 + get_displaced_wave_height(Vec2::new(local.x, local.z), &waves.waves, time, &settings);
+    assert!((height - expected).abs() < 1e-3, "{height} != {expected}");
+    assert!((still_level - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn batched_heights_match_single_queries() {
+    let transform = Transform::from_xyz(-4.0, 12.0, 6.0).with_rotation(Quat::from_rotation_y(-0.7));
+    let mut app = app_with_water(transform);
+    let points: Vec<Vec3> = (0..11).map(|i| Vec3::new(i as f32 * 3.7 - 20.0, 12.0, i as f32 * -2.1)).collect();
+
+    let (batched, single) = app
+        .world_mut()
+        .run_system_once(move |mut water: WaterQuery| {
+            let mut heights = vec![0.0; points.len()];
+            assert!(water.heights(&points, &mut heights));
+            let single: Vec<f32> = points.iter().map(|point| water.height(*point).unwrap()).collect();
+            (heights, single)
+        })
+        .unwrap();
+
+    for (batched, single) in batched.iter().zip(&single) {
+        assert!((batched - single).abs() < 1e-3, "{batched} != {single}");
+    }
+}
+
+#[test]
+fn surfboard_floats_on_raised_lake() {
+    let lake = Transform::from_xyz(0.0, 50.0, 0.0);
+    let mut app = app_with_water(lake);
+    app.add_systems(Update, update_surfboard_physics);
+    // A calm lake, so the board settles at its draft,
+    app.world_mut().query::<&mut WaterWaves>().single_mut(app.world_mut()).unwrap().waves.clear();
+    // with a heavily damped board so that happens quickly
+    let floating_body = FloatingBody {
+        drag_coefficient: 10.0,
+        ..default()
+    };
+    let surfboard = Surfboard::default();
+    let mass = surfboard.mass_properties(floating_body.body_density).mass;
+    let volume: f32 = floating_body.point_volumes.iter().sum();
+    let height = floating_body.point_height;
+    let submerged = mass / (floating_body.water_density * volume);
+    // The point volumes fill from the bottom, half their height below the board's origin
+    let expected = lake.translation.y + height / 2.0 - submerged * height;
+    let board = app.world_mut().spawn((Transform::from_xyz(0.0, 52.0, 0.0), floating_body, surfboard)).id();
+
+    for _ in 0..1500 {
+        app.update();
+    }
+
+    let board = app.world().get::<Transform>(board).unwrap().translation.y;
+    assert!((board - expected).abs() < 0.01, "board ended up at y={board}, expected {expected}");
+}